@@ -0,0 +1,78 @@
+use std::collections::hash_map::DefaultHasher;
+use std::env;
+use std::fs;
+use std::hash::Hasher;
+use std::path::{Path, PathBuf};
+
+// Every `src/days/dayNN.rs` file is a day: this generates its `mod` declaration
+// and its entry in the registry, so adding a day only takes adding its file.
+// The file is expected to define a `DayNN` solver.
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let days_dir = Path::new(&manifest_dir).join("src").join("days");
+    let util_dir = Path::new(&manifest_dir).join("src").join("util");
+    let lib_file = Path::new(&manifest_dir).join("src").join("lib.rs");
+    for path in [&days_dir, &util_dir, &lib_file] {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+
+    let mut days = Vec::new();
+    for entry in fs::read_dir(&days_dir).expect("could not read src/days") {
+        let path = entry.unwrap().path();
+        let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let Some(number) = name.strip_prefix("day") else {
+            continue;
+        };
+        if path.extension().is_none_or(|ext| ext != "rs")
+            || number.len() != 2
+            || !number.bytes().all(|b| b.is_ascii_digit())
+        {
+            continue;
+        }
+        let day: u8 = number.parse().unwrap();
+        assert!(
+            (1..=25).contains(&day),
+            "{} is not a valid day",
+            path.display()
+        );
+        days.push((day, path));
+    }
+    days.sort();
+
+    let mut code = String::new();
+    for (day, path) in &days {
+        code += &format!(
+            "#[path = {:?}]\npub mod day{:02};\n",
+            path.display().to_string(),
+            day
+        );
+    }
+    code += "\npub static DAYS: &[(u8, &dyn crate::DaySolver)] = &[\n";
+    for (day, _) in &days {
+        code += &format!("    ({}, &day{:02}::Day{:02}),\n", day, day, day);
+    }
+    code += "];\n";
+
+    // A day's fingerprint changes with its source and with the library code it
+    // builds on, which tells the result cache when its answers may have changed
+    let mut shared: Vec<PathBuf> = fs::read_dir(&util_dir)
+        .expect("could not read src/util")
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    shared.push(lib_file);
+    shared.sort();
+    code += "\npub static FINGERPRINTS: &[(u8, u64)] = &[\n";
+    for (day, path) in &days {
+        let mut hasher = DefaultHasher::new();
+        for file in shared.iter().chain([path]) {
+            hasher.write(&fs::read(file).unwrap());
+        }
+        code += &format!("    ({}, {:#018x}),\n", day, hasher.finish());
+    }
+    code += "];\n";
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("days.rs"), code).unwrap();
+}
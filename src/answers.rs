@@ -0,0 +1,80 @@
+use aoc::Solution;
+
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+// Known answers are stored next to the input they belong to, e.g.
+// `inputs/day05.answers` or `inputs/examples/day05.answers`:
+//
+//   part1: 3
+//   part2: 14
+//
+// Either line can be left out if that answer isn't known yet.
+#[derive(Default)]
+pub struct Answers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+pub enum Verdict {
+    Pass,
+    Fail(String),
+    Unknown,
+}
+
+impl Answers {
+    pub fn load(path: Option<&Path>) -> Result<Self, String> {
+        let Some(path) = path else {
+            return Ok(Self::default());
+        };
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(format!("Could not read {}: {}", path.display(), err)),
+        };
+
+        let mut answers = Self::default();
+        for (index, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match line.split_once(':') {
+                Some(("part1", value)) => answers.part1 = Some(value.trim().to_string()),
+                Some(("part2", value)) => answers.part2 = Some(value.trim().to_string()),
+                _ => {
+                    return Err(format!(
+                        "{}:{}: expected `part1: <answer>` or `part2: <answer>`",
+                        path.display(),
+                        index + 1
+                    ));
+                }
+            }
+        }
+        Ok(answers)
+    }
+
+    pub fn check(&self, part: u8, solution: &Solution) -> Verdict {
+        let expected = match part {
+            1 => &self.part1,
+            2 => &self.part2,
+            _ => unreachable!(),
+        };
+        match expected {
+            Some(expected) if *expected == solution.to_string() => Verdict::Pass,
+            Some(expected) => Verdict::Fail(expected.clone()),
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail(expected) => write!(f, "FAIL, expected {}", expected),
+            Verdict::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use std::fmt::{Display, Formatter, Result};
+
+pub struct Stats {
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub p95: f64,
+}
+
+impl Stats {
+    // Expects at least one sample
+    pub fn from_samples(samples: &[f64]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let n = sorted.len();
+
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+        } else {
+            sorted[n / 2]
+        };
+        let mean = sorted.iter().sum::<f64>() / n as f64;
+        let variance = sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+        // Nearest-rank percentile
+        let p95_rank = (0.95 * n as f64).ceil() as usize;
+
+        Stats {
+            min: sorted[0],
+            median,
+            mean,
+            std_dev: variance.sqrt(),
+            p95: sorted[p95_rank.max(1) - 1],
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "min {:.4} ms · median {:.4} ms · mean {:.4} ± {:.4} ms · p95 {:.4} ms",
+            self.min, self.median, self.mean, self.std_dev, self.p95
+        )
+    }
+}
@@ -0,0 +1,192 @@
+use crate::cli::Options;
+use crate::history;
+use crate::report::{self, DayReport};
+use crate::{DayInput, DayRun};
+
+use aoc::Solution;
+use aoc::days;
+
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::ErrorKind;
+use std::path::Path;
+
+// Answers of the last successful run of each day, reused as long as neither
+// its input nor its code changed
+pub const CACHE_PATH: &str = ".aoc/cache.csv";
+const HEADER: &str = "day,variant,part,example,key,parse_ms,params,part1_type,part1_answer,part1_ms,part2_type,part2_answer,part2_ms";
+
+pub struct CachedRun {
+    day: u8,
+    variant: String,
+    part: Option<u8>,
+    example: bool,
+    key: u64,
+    parse_ms: Option<f64>,
+    // Parameter overrides the day used
+    params: Vec<String>,
+    sols: [Option<(Solution, f64)>; 2],
+}
+
+#[derive(Default)]
+pub struct Cache {
+    runs: Vec<CachedRun>,
+}
+
+impl CachedRun {
+    // Only the last run of a day is kept, for each variant, part selection and
+    // kind of input
+    fn same_slot(&self, other: &CachedRun) -> bool {
+        self.day == other.day
+            && self.variant == other.variant
+            && self.part == other.part
+            && self.example == other.example
+    }
+
+    // Cache entry of a successful run
+    pub fn from_report(report: &DayReport, variant: &str, key: u64) -> Self {
+        CachedRun {
+            day: report.day,
+            variant: variant.to_string(),
+            part: report.part,
+            example: report.is_example,
+            key,
+            parse_ms: report.parse.as_ref().map(|parse| parse.time_ms),
+            params: report.params.iter().map(|(name, _)| name.clone()).collect(),
+            sols: report.parts.each_ref().map(|sol| {
+                sol.as_ref()
+                    .map(|sol| (sol.solution.clone(), sol.timing.time_ms))
+            }),
+        }
+    }
+
+    pub fn to_run(&self) -> DayRun {
+        DayRun {
+            parse_ms: self.parse_ms,
+            sols: self.sols.clone(),
+            allocs: [None; 3],
+            used_params: self.params.clone(),
+            profile: Vec::new(),
+            events: Vec::new(),
+            error: None,
+        }
+    }
+}
+
+impl Cache {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Cache::default()),
+            Err(err) => return Err(format!("Could not read {}: {}", path.display(), err)),
+        };
+
+        let invalid =
+            |index: usize| format!("{}:{}: invalid cache entry", path.display(), index + 1);
+        let runs = content
+            .lines()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(index, line)| {
+                let mut fields = history::split_csv_line(line).into_iter();
+                let mut next = || fields.next().ok_or_else(|| invalid(index));
+                let day = next()?.parse().map_err(|_| invalid(index))?;
+                let variant = next()?;
+                let part = match next()?.as_str() {
+                    "" => None,
+                    part => Some(part.parse().map_err(|_| invalid(index))?),
+                };
+                let example = next()?.parse().map_err(|_| invalid(index))?;
+                let key = u64::from_str_radix(&next()?, 16).map_err(|_| invalid(index))?;
+                let parse_ms = match next()?.as_str() {
+                    "" => None,
+                    time => Some(time.parse().map_err(|_| invalid(index))?),
+                };
+                let params = next()?
+                    .split(';')
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                let mut sol = || -> Result<_, String> {
+                    let (kind, answer, time) = (next()?, next()?, next()?);
+                    if kind.is_empty() {
+                        return Ok(None);
+                    }
+                    let solution =
+                        Solution::from_kind(&kind, &answer).ok_or_else(|| invalid(index))?;
+                    Ok(Some((solution, time.parse().map_err(|_| invalid(index))?)))
+                };
+                Ok(CachedRun {
+                    day,
+                    variant,
+                    part,
+                    example,
+                    key,
+                    parse_ms,
+                    params,
+                    sols: [sol()?, sol()?],
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Cache { runs })
+    }
+
+    pub fn get(&self, key: u64) -> Option<&CachedRun> {
+        self.runs.iter().find(|run| run.key == key)
+    }
+
+    pub fn insert(&mut self, run: CachedRun) {
+        self.runs.retain(|other| !other.same_slot(&run));
+        self.runs.push(run);
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let error = |err: std::io::Error| format!("Could not write {}: {}", path.display(), err);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(error)?;
+        }
+        let mut content = format!("{}\n", HEADER);
+        for run in &self.runs {
+            content.push_str(&format!(
+                "{},{},{},{},{:016x},{},{}",
+                run.day,
+                report::csv_field(&run.variant),
+                run.part.map_or(String::new(), |part| part.to_string()),
+                run.example,
+                run.key,
+                run.parse_ms
+                    .map_or(String::new(), |time| format!("{:.6}", time)),
+                report::csv_field(&run.params.join(";")),
+            ));
+            for sol in &run.sols {
+                match sol {
+                    Some((solution, time)) => content.push_str(&format!(
+                        ",{},{},{:.6}",
+                        solution.kind(),
+                        report::csv_field(&solution.to_string()),
+                        time
+                    )),
+                    None => content.push_str(",,,"),
+                }
+            }
+            content.push('\n');
+        }
+        fs::write(path, content).map_err(error)
+    }
+}
+
+// Identifies a run of `day` with `variant`: a cached run with the same key had
+// the same code, input, parameters and part selection
+pub fn key(day: u8, variant: &str, input: &DayInput, options: &Options) -> Option<u64> {
+    let mut hasher = DefaultHasher::new();
+    days::fingerprint(day)?.hash(&mut hasher);
+    (day, variant, options.part, input.is_example).hash(&mut hasher);
+    input.part1.hash(&mut hasher);
+    input.part2.hash(&mut hasher);
+    let mut params: Vec<_> = options.params.iter().collect();
+    params.sort();
+    params.hash(&mut hasher);
+    Some(hasher.finish())
+}
@@ -0,0 +1,397 @@
+use crate::report::Format;
+
+use aoc::days;
+use aoc::{InputSource, Level};
+
+use std::collections::HashMap;
+use std::env;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+pub const USAGE: &str = "Usage: advent_of_code_2025 [OPTIONS] <DAYS>...
+       advent_of_code_2025 new-day <DAY> [TITLE]
+       advent_of_code_2025 fetch <DAYS>...
+
+Days can be given as:
+  N      a single day, e.g. 7
+  A-B    an inclusive range of days, e.g. 1-5
+  all    every implemented day
+
+new-day creates src/days/dayNN.rs from a template along with empty input and
+example files, without overwriting any existing file.
+
+fetch downloads the inputs of the given days that aren't in inputs/ yet, using
+the session token from AOC_SESSION or .aoc/session. AOC_CONTACT is sent in the
+User-Agent so that the server knows who to contact, e.g. your email address or
+repository URL. Set AOC_BASE_URL to download from another server than
+https://adventofcode.com.
+
+Set EXAMPLE=1 to use the example inputs instead, and AOC_INPUT_DIR to read the
+inputs from another directory than inputs/. When built with the alloc-stats
+feature, the allocations of each phase are shown next to its time.
+
+Options:
+  --list        list the implemented days and exit
+  --part P      only run part P (1 or 2) of each day
+  --timeout S   give up on a day after S seconds, keeping the parts it solved.
+                A day that doesn't poll should_stop keeps running in the
+                background, which can slow down the days after it
+  --profile     show the time spent in the spans of each day
+  --param K=V   override the puzzle parameter K of the days that use it,
+                e.g. --param connections=500 (can be repeated); the answers
+                of those days aren't checked
+  -v, -vv       show the debug, or debug and trace, output of the solvers
+  --log FILE    write the debug output to FILE instead (add -vv for traces)
+  --variant V   run the implementation named V of each day, see --list
+  --compare     run every implementation of each day, check that their
+                answers agree and compare their times
+  --compare-last
+                flag the parts whose answer changed since the previous run, or
+                whose time is above the median of their last 5 runs timed the
+                same way (with or without --bench), from .aoc/history.csv
+  --threshold P slowdown in percent flagged by --compare-last (default: 10)
+  --watch       run the days again whenever their input, example or answers
+                files change, showing how the answers and times changed
+  --no-cache    run the days even when their code and input didn't change
+                since their last successful run, whose answers are otherwise
+                shown from .aoc/cache.csv (marked as cached in every format)
+  --bench N     run each day N times and report timing statistics
+  --warmup N    untimed runs before benchmarking (default: N / 10, at least 1)
+  --input PATH  read the input from a file, a directory of dayNN.txt files,
+                or stdin when PATH is -
+  --input-str S use S itself as the input, e.g. --input-str $'L68\\nR48'
+  --format F    output format: text (default), json or csv
+  -j, --jobs N  number of days to run concurrently (default: number of CPUs,
+                or 1 when benchmarking)";
+
+pub struct Options {
+    pub days: Vec<u8>,
+    pub part: Option<u8>,
+    pub variant: Option<String>,
+    pub compare: bool,
+    // Threshold of --compare-last, as a fraction
+    pub compare_last: Option<f64>,
+    pub no_cache: bool,
+    pub watch: bool,
+    pub timeout: Option<Duration>,
+    pub params: HashMap<String, String>,
+    pub log_level: Option<Level>,
+    pub log_file: Option<PathBuf>,
+    pub bench: Option<Bench>,
+    pub profile: bool,
+    pub format: Format,
+    pub jobs: usize,
+    pub input: Option<InputSource>,
+    pub example: bool,
+    pub list: bool,
+}
+
+#[derive(Clone, Copy)]
+pub struct Bench {
+    pub runs: usize,
+    pub warmup: usize,
+}
+
+pub fn parse_args<I>(args: I) -> Result<Options, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut days = Vec::new();
+    let mut part = None;
+    let mut variant = None;
+    let mut compare = false;
+    let mut compare_last = false;
+    let mut threshold = None;
+    let mut no_cache = false;
+    let mut watch = false;
+    let mut timeout = None;
+    let mut params = HashMap::new();
+    let mut log_level = None;
+    let mut log_file = None;
+    let mut bench_runs = None;
+    let mut profile = false;
+    let mut warmup = None;
+    let mut format = Format::Text;
+    let mut jobs = None;
+    let mut input = None;
+    let mut list = false;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if arg.starts_with("--") => {
+                (flag.to_string(), Some(value.to_string()))
+            }
+            _ => (arg.clone(), None),
+        };
+        let mut value = || {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("Missing value for {}", flag))
+        };
+
+        match flag.as_str() {
+            "--list" => list = true,
+            "--part" => part = Some(parse_part(&value()?)?),
+            "--variant" => variant = Some(value()?),
+            "--compare" => compare = true,
+            "--compare-last" => compare_last = true,
+            "--threshold" => threshold = Some(parse_threshold(&value()?)?),
+            "--no-cache" => no_cache = true,
+            "--watch" => watch = true,
+            "--timeout" => timeout = Some(parse_timeout(&value()?)?),
+            "--profile" => profile = true,
+            "--param" => {
+                let value = value()?;
+                let Some((name, param)) = value.split_once('=') else {
+                    return Err(format!(
+                        "Invalid value for --param: {} (expected K=V)",
+                        value
+                    ));
+                };
+                params.insert(name.to_string(), param.to_string());
+            }
+            "-v" if log_level.is_some() => log_level = Some(Level::Trace),
+            "-v" => log_level = Some(Level::Debug),
+            "-vv" => log_level = Some(Level::Trace),
+            "--log" => log_file = Some(PathBuf::from(value()?)),
+            "--bench" => bench_runs = Some(parse_count(&flag, &value()?)?),
+            "--warmup" => warmup = Some(parse_number(&flag, &value()?)?),
+            "--format" => format = parse_format(&value()?)?,
+            "--input" | "--input-str" if input.is_some() => {
+                return Err("Only one --input or --input-str can be given".to_string());
+            }
+            "--input" => input = Some(InputSource::from_arg(&value()?)),
+            "--input-str" => input = Some(InputSource::Inline(value()?)),
+            "-j" | "--jobs" => jobs = Some(parse_count(&flag, &value()?)?),
+            _ if flag.starts_with('-') => return Err(format!("Unknown option: {}", flag)),
+            _ => days.extend(parse_day_selection(&arg)?),
+        }
+    }
+
+    if days.is_empty() && !list {
+        return Err("Please provide the day(s) to run.".to_string());
+    }
+
+    if warmup.is_some() && bench_runs.is_none() {
+        return Err("--warmup only makes sense together with --bench".to_string());
+    }
+
+    // Benchmarks are more reliable without other days competing for the CPU
+    let jobs = jobs.unwrap_or_else(|| match bench_runs {
+        Some(_) => 1,
+        None => thread::available_parallelism().map_or(1, |n| n.get()),
+    });
+
+    days.sort_unstable();
+    days.dedup();
+
+    if threshold.is_some() && !compare_last {
+        return Err("--threshold only makes sense together with --compare-last".to_string());
+    }
+
+    if compare && (variant.is_some() || format != Format::Text) {
+        return Err("--compare can't be used with --variant or --format".to_string());
+    }
+
+    if watch && (compare || compare_last || format != Format::Text || log_file.is_some()) {
+        return Err(
+            "--watch can't be used with --compare, --compare-last, --format or --log".to_string(),
+        );
+    }
+
+    if watch && matches!(input, Some(InputSource::Stdin)) {
+        return Err("--watch can't read the input from stdin".to_string());
+    }
+
+    if let Some(variant) = &variant {
+        for &day in &days {
+            if days::get_variant(day, variant).is_none() {
+                let available: Vec<&str> = days::get_variants(day)
+                    .iter()
+                    .map(|solver| solver.variant())
+                    .collect();
+                return Err(format!(
+                    "Day {} has no {} variant (available: {})",
+                    day,
+                    variant,
+                    available.join(", ")
+                ));
+            }
+        }
+    }
+
+    if let Some(input) = &input
+        && !input.is_per_day()
+        && days.len() > 1
+    {
+        return Err(format!(
+            "{} can only be used as the input of a single day",
+            input
+        ));
+    }
+
+    Ok(Options {
+        days,
+        part,
+        variant,
+        compare,
+        compare_last: compare_last.then(|| threshold.unwrap_or(10.0) / 100.0),
+        no_cache,
+        watch,
+        timeout,
+        params,
+        // A log file is pointless without any output
+        log_level: match log_file {
+            Some(_) => log_level.or(Some(Level::Debug)),
+            None => log_level,
+        },
+        log_file,
+        bench: bench_runs.map(|runs| Bench {
+            runs,
+            warmup: warmup.unwrap_or((runs / 10).max(1)),
+        }),
+        profile,
+        format,
+        jobs,
+        input,
+        example: env::var_os("EXAMPLE").is_some(),
+        list,
+    })
+}
+
+// Arguments of `new-day`: the day and the title of its puzzle
+pub fn parse_new_day(args: &[String]) -> Result<(u8, String), String> {
+    match args {
+        [day] => Ok((parse_day(day, day)?, "TODO".to_string())),
+        [day, title] => Ok((parse_day(day, day)?, title.clone())),
+        _ => Err("Usage: new-day <DAY> [TITLE]".to_string()),
+    }
+}
+
+// Days of `fetch`, which don't need to be implemented yet
+pub fn parse_fetch(args: &[String]) -> Result<Vec<u8>, String> {
+    if args.is_empty() {
+        return Err("Please provide the day(s) to fetch.".to_string());
+    }
+    let mut days = Vec::new();
+    for arg in args {
+        days.extend(parse_day_range(arg)?);
+    }
+    days.sort_unstable();
+    days.dedup();
+    Ok(days)
+}
+
+fn parse_format(value: &str) -> Result<Format, String> {
+    match value {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        "csv" => Ok(Format::Csv),
+        _ => Err(format!(
+            "Unknown format: {} (expected text, json or csv)",
+            value
+        )),
+    }
+}
+
+fn parse_part(value: &str) -> Result<u8, String> {
+    match value {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!(
+            "Invalid value for --part: {} (expected 1 or 2)",
+            value
+        )),
+    }
+}
+
+fn parse_timeout(value: &str) -> Result<Duration, String> {
+    match value.parse::<f64>() {
+        Ok(seconds) if seconds > 0.0 && seconds.is_finite() => Ok(Duration::from_secs_f64(seconds)),
+        _ => Err(format!(
+            "Invalid value for --timeout: {} (expected seconds)",
+            value
+        )),
+    }
+}
+
+fn parse_threshold(value: &str) -> Result<f64, String> {
+    match value.trim_end_matches('%').parse::<f64>() {
+        Ok(percent) if percent >= 0.0 && percent.is_finite() => Ok(percent),
+        _ => Err(format!(
+            "Invalid value for --threshold: {} (expected a percentage)",
+            value
+        )),
+    }
+}
+
+fn parse_number(flag: &str, value: &str) -> Result<usize, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value for {}: {}", flag, value))
+}
+
+fn parse_count(flag: &str, value: &str) -> Result<usize, String> {
+    match parse_number(flag, value)? {
+        0 => Err(format!("{} needs to be at least 1", flag)),
+        count => Ok(count),
+    }
+}
+
+fn parse_day_selection(arg: &str) -> Result<Vec<u8>, String> {
+    if arg == "all" {
+        return Ok(days::available_days().collect());
+    }
+
+    let selected = parse_day_range(arg)?;
+    for &day in &selected {
+        if days::get_day_solver(day).is_none() {
+            return Err(format!(
+                "Day {} is not implemented (available: {})",
+                day,
+                available_days_summary()
+            ));
+        }
+    }
+
+    Ok(selected)
+}
+
+// A single day or an inclusive range of days, e.g. 7 or 1-5
+fn parse_day_range(arg: &str) -> Result<Vec<u8>, String> {
+    match arg.split_once('-') {
+        Some((start, end)) => {
+            let start = parse_day(start, arg)?;
+            let end = parse_day(end, arg)?;
+            if start > end {
+                return Err(format!("Invalid day range: {} (start is after end)", arg));
+            }
+            Ok((start..=end).collect())
+        }
+        None => Ok(vec![parse_day(arg, arg)?]),
+    }
+}
+
+fn parse_day(value: &str, arg: &str) -> Result<u8, String> {
+    match value.parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!("Not a valid day: {}", arg)),
+    }
+}
+
+fn available_days_summary() -> String {
+    let days: Vec<u8> = days::available_days().collect();
+    match (days.first(), days.last()) {
+        (Some(first), Some(last)) if (last - first) as usize + 1 == days.len() => {
+            format!("{}-{}", first, last)
+        }
+        _ => days
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
@@ -0,0 +1,88 @@
+use crate::answers::Verdict;
+use crate::cli::Options;
+use crate::report::{self, DayReport, PartReport};
+use crate::{pool, solve_day};
+
+use aoc::days;
+
+// Runs every implementation of the selected days and prints their times side by
+// side. Returns the number of days where an implementation failed or where the
+// implementations disagree on an answer.
+pub fn compare_days(options: &Options) -> usize {
+    let mut failures = 0;
+    pool::for_each_ordered(
+        &options.days,
+        options.jobs,
+        |&day| {
+            days::get_variants(day)
+                .into_iter()
+                .map(|solver| solve_day(day, solver, options, None))
+                .collect::<Vec<_>>()
+        },
+        |reports| {
+            if !print_comparison(&reports) {
+                failures += 1;
+            }
+        },
+    );
+    failures
+}
+
+// Prints the timing table of a day and whether its implementations agree
+fn print_comparison(reports: &[DayReport]) -> bool {
+    report::print_header(reports[0].day, None);
+    println!(
+        "  {:<14} {:>14} {:>14} {:>14}",
+        "Variant", "Parse", "Part 1", "Part 2"
+    );
+    let time = |time_ms: Option<f64>| time_ms.map_or("-".to_string(), |ms| format!("{:.4} ms", ms));
+    for report in reports {
+        let parts = report
+            .parts
+            .each_ref()
+            .map(|sol| time(sol.as_ref().map(|sol| sol.timing.elapsed_ms())));
+        println!(
+            "  {:<14} {:>14} {:>14} {:>14}",
+            report.variant.unwrap_or_default(),
+            time(report.parse.as_ref().map(|parse| parse.elapsed_ms())),
+            parts[0],
+            parts[1]
+        );
+    }
+
+    let mut ok = true;
+    for report in reports {
+        if let Some(error) = &report.error {
+            println!("  · {}: {}", report.variant.unwrap_or_default(), error);
+            ok = false;
+        }
+    }
+    for part in [1, 2] {
+        let answers: Vec<(&str, &PartReport)> = reports
+            .iter()
+            .filter_map(|report| {
+                let sol = report.parts[part - 1].as_ref()?;
+                Some((report.variant.unwrap_or_default(), sol))
+            })
+            .collect();
+        let Some((_, first)) = answers.first() else {
+            continue;
+        };
+        if answers
+            .iter()
+            .all(|(_, sol)| sol.solution.to_string() == first.solution.to_string())
+        {
+            println!("  · Part {}: {} [{}]", part, first.solution, first.verdict);
+            ok &= !matches!(first.verdict, Verdict::Fail(_));
+        } else {
+            let answers: Vec<String> = answers
+                .iter()
+                .map(|(variant, sol)| format!("{} {}", variant, sol.solution))
+                .collect();
+            println!("  · Part {}: MISMATCH, {}", part, answers.join(", "));
+            ok = false;
+        }
+    }
+    println!();
+    ok
+}
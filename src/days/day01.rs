@@ -1,4 +1,4 @@
-use aoc::Context;
+use crate::{Context, Error, Result, Solution, Solver, parse_field};
 
 struct Dial {
     position: i32,
@@ -55,25 +55,63 @@ impl Dial {
     }
 }
 
-pub fn solve(ctx: &mut Context) {
+pub enum Rotation {
+    Right(i32),
+    Left(i32),
+}
+
+// Turns the dial through every rotation, returning how many rotations ended
+// at zero along with the dial itself
+fn turn_dial(rotations: &[Rotation]) -> (i32, Dial) {
     let mut dial = Dial::new(50);
-    let mut result1 = 0;
-    for line in ctx.input() {
-        // first character indicates direction, rest is number of steps
-        let direction = line.chars().next().unwrap();
-        let steps: i32 = line[1..].parse().unwrap();
-        match direction {
-            'R' => dial.turn_right(steps),
-            'L' => dial.turn_left(steps),
-            _ => panic!("Invalid direction"),
+    let mut stops_at_zero = 0;
+    for rotation in rotations {
+        match *rotation {
+            Rotation::Right(steps) => dial.turn_right(steps),
+            Rotation::Left(steps) => dial.turn_left(steps),
         }
         if dial.current_position() == 0 {
-            result1 += 1;
+            stops_at_zero += 1;
         }
         if dial.current_position() < 0 || dial.current_position() > Dial::MAX_VALUE {
             panic!("Dial position out of bounds");
         }
     }
-    ctx.set_sol1(result1);
-    ctx.set_sol2(dial.times_at_zero);
+    (stops_at_zero, dial)
+}
+
+pub struct Day01;
+
+impl Solver for Day01 {
+    type Parsed = Vec<Rotation>;
+
+    const TITLE: &'static str = "Secret Entrance";
+
+    fn parse(&self, input: &[String]) -> Result<Self::Parsed> {
+        input
+            .iter()
+            .enumerate()
+            .map(|(index, line)| {
+                // first character indicates direction, rest is number of steps
+                match line.split_at_checked(1) {
+                    Some(("R", steps)) => Ok(Rotation::Right(parse_field(index, line, steps)?)),
+                    Some(("L", steps)) => Ok(Rotation::Left(parse_field(index, line, steps)?)),
+                    _ => Err(Error::at(
+                        index,
+                        line,
+                        line,
+                        "expected a direction (R or L)",
+                    )),
+                }
+            })
+            .collect()
+    }
+
+    fn part1(&self, rotations: &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        Ok(turn_dial(rotations).0.into())
+    }
+
+    fn part2(&self, rotations: &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        Ok(turn_dial(rotations).1.times_at_zero.into())
+    }
 }
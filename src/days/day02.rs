@@ -1,12 +1,10 @@
-use aoc::Context;
+use crate::{Context, Error, Result, Solution, Solver, parse_field};
 
 use std::collections::HashSet;
 
-pub fn solve_range(range: &(&str, &str)) -> (u64, u64) {
-    let (start, end) = range;
-    let n_digits_end = end.len();
-    let start_num: u64 = start.parse().unwrap();
-    let end_num: u64 = end.parse().unwrap();
+pub fn solve_range(range: &(u64, u64)) -> (u64, u64) {
+    let &(start_num, end_num) = range;
+    let n_digits_end = end_num.to_string().len();
 
     let digits = (0..=9).map(|d| d.to_string()).collect::<Vec<String>>();
 
@@ -70,22 +68,47 @@ pub fn solve_range(range: &(&str, &str)) -> (u64, u64) {
     (solution_set.iter().sum(), solution_second_set.iter().sum())
 }
 
-pub fn solve(ctx: &mut Context) {
-    let ranges: Vec<(&str, &str)> = ctx.input()[0]
-        .split(',')
-        .map(|x| {
-            let mut range = x.split('-');
-            let start = range.next().unwrap();
-            let end = range.next().unwrap();
-            (start, end)
-        })
-        .collect();
-
-    let (result1, result2) = ranges
-        .iter()
-        .map(solve_range)
-        .reduce(|(acc1, acc2), (x1, x2)| (acc1 + x1, acc2 + x2))
-        .unwrap();
-    ctx.set_sol1(result1);
-    ctx.set_sol2(result2);
+// Sums of the invalid IDs of both parts. They come out of the same search,
+// which is done once while parsing so that its time shows up as shared work.
+pub struct ProductRanges {
+    sums: (u64, u64),
+}
+
+pub struct Day02;
+
+impl Solver for Day02 {
+    type Parsed = ProductRanges;
+
+    const TITLE: &'static str = "Gift Shop";
+
+    fn parse(&self, input: &[String]) -> Result<Self::Parsed> {
+        let line = input
+            .first()
+            .ok_or_else(|| Error::missing_section("ranges"))?;
+        let ranges: Vec<(u64, u64)> = line
+            .split(',')
+            .map(|x| {
+                let mut range = x.split('-');
+                let start = parse_field(0, line, range.next().unwrap_or(x))?;
+                let end = range
+                    .next()
+                    .ok_or_else(|| Error::at(0, line, &x[x.len()..], "missing range end"))?;
+                let end = parse_field(0, line, end)?;
+                Ok((start, end))
+            })
+            .collect::<Result<_>>()?;
+        let sums = ranges
+            .iter()
+            .map(solve_range)
+            .fold((0, 0), |(first, second), (a, b)| (first + a, second + b));
+        Ok(ProductRanges { sums })
+    }
+
+    fn part1(&self, ranges: &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        Ok(ranges.sums.0.into())
+    }
+
+    fn part2(&self, ranges: &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        Ok(ranges.sums.1.into())
+    }
 }
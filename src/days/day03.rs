@@ -1,14 +1,12 @@
-use aoc::Context;
+use crate::{Context, Error, Result, Solution, Solver};
 
-fn solve1(ctx: &Context) -> u32 {
+fn solve1(banks: &[Vec<u32>]) -> u32 {
     let mut result = 0;
-    for line in ctx.input() {
-        let chars = line.chars().collect::<Vec<char>>();
-        let size = line.len();
+    for bank in banks {
+        let size = bank.len();
         let mut largest = 0;
         let mut largest_pos = 0;
-        for (i, &ch) in chars.iter().enumerate().take(size - 1) {
-            let num = ch.to_digit(10).unwrap();
+        for (i, &num) in bank.iter().enumerate().take(size - 1) {
             if num > largest {
                 largest = num;
                 largest_pos = i;
@@ -16,8 +14,7 @@ fn solve1(ctx: &Context) -> u32 {
         }
 
         let mut next_largest = 0;
-        for (_, &ch) in chars.iter().enumerate().skip(largest_pos + 1) {
-            let num = ch.to_digit(10).unwrap();
+        for &num in bank.iter().skip(largest_pos + 1) {
             if num > next_largest {
                 next_largest = num;
             }
@@ -52,26 +49,26 @@ fn pick_largest_digit(
         .unwrap()
 }
 
-fn solve2(ctx: &Context) -> u64 {
+fn solve2(banks: &[Vec<u32>]) -> u64 {
     // in this one we need to select the 12 largest digits from each line in the order
     // they appear, without reordering
     let mut result: u64 = 0;
-    for line in ctx.input() {
-        let numbers = line
-            .chars()
+    for bank in banks {
+        let numbers = bank
+            .iter()
             .enumerate()
-            .map(|(index, ch)| (ch.to_digit(10).unwrap() as u64, index))
+            .map(|(index, &digit)| (digit as u64, index))
             .collect::<Vec<(u64, usize)>>();
 
         // Sort by digit descending
         let mut sorted_numbers = numbers.clone();
-        sorted_numbers.sort_by(|a, b| b.0.cmp(&a.0));
+        sorted_numbers.sort_by_key(|n| std::cmp::Reverse(n.0));
 
         let mut last_index = 0;
         let mut line_result = 0;
         for i in 0..12 {
             let (digit, index) =
-                pick_largest_digit(&sorted_numbers, last_index, line.len() - (12 - i));
+                pick_largest_digit(&sorted_numbers, last_index, bank.len() - (12 - i));
             line_result = line_result * 10 + digit;
             last_index = index + 1;
         }
@@ -80,7 +77,41 @@ fn solve2(ctx: &Context) -> u64 {
     result
 }
 
-pub fn solve(ctx: &mut Context) {
-    ctx.set_sol1(solve1(ctx));
-    ctx.set_sol2(solve2(ctx));
+pub struct Day03;
+
+impl Solver for Day03 {
+    // One bank of single digit batteries per line
+    type Parsed = Vec<Vec<u32>>;
+
+    const TITLE: &'static str = "Lobby";
+
+    fn parse(&self, input: &[String]) -> Result<Self::Parsed> {
+        input
+            .iter()
+            .enumerate()
+            .map(|(index, line)| {
+                if let Some(pos) = line.find(|c: char| !c.is_ascii_digit()) {
+                    return Err(Error::at(index, line, &line[pos..], "expected a digit"));
+                }
+                // Part 2 turns on 12 batteries in every bank
+                if line.len() < 12 {
+                    return Err(Error::at(
+                        index,
+                        line,
+                        &line[line.len()..],
+                        "expected at least 12 batteries",
+                    ));
+                }
+                Ok(line.chars().map(|ch| ch.to_digit(10).unwrap()).collect())
+            })
+            .collect()
+    }
+
+    fn part1(&self, banks: &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        Ok(solve1(banks).into())
+    }
+
+    fn part2(&self, banks: &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        Ok(solve2(banks).into())
+    }
 }
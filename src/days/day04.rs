@@ -1,6 +1,6 @@
-use aoc::Context;
+use crate::{Context, Result, Solution, Solver};
 
-use aoc::Grid;
+use crate::Grid;
 
 pub fn remove_rolls(grid: &mut Grid<u8>) -> u32 {
     let mut to_remove = Vec::new();
@@ -32,20 +32,47 @@ pub fn remove_rolls(grid: &mut Grid<u8>) -> u32 {
     n_removed
 }
 
-pub fn solve(ctx: &mut Context) {
-    let mut grid = Grid::parse(ctx.input());
+// Both parts start with the same pass, which is done once while parsing so
+// that its time shows up as shared work
+pub struct Rolls {
+    // Rolls removed by the first pass and the grid left after it
+    first_removed: u32,
+    grid: Grid<u8>,
+}
+
+pub struct Day04;
+
+impl Solver for Day04 {
+    type Parsed = Rolls;
 
-    // First iteration is solution to problem 1
-    let mut total_removed = remove_rolls(&mut grid);
-    ctx.set_sol1(total_removed);
+    const TITLE: &'static str = "Printing Department";
+
+    fn parse(&self, input: &[String]) -> Result<Self::Parsed> {
+        let mut grid = Grid::try_parse(input)?;
+        let first_removed = remove_rolls(&mut grid);
+        Ok(Rolls {
+            first_removed,
+            grid,
+        })
+    }
 
-    // Repeat until we reach a stable state
-    loop {
-        let n_removed = remove_rolls(&mut grid);
-        if n_removed == 0 {
-            break;
+    fn part1(&self, rolls: &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        // Only the first iteration
+        Ok(rolls.first_removed.into())
+    }
+
+    fn part2(&self, rolls: &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        let mut total_removed = rolls.first_removed;
+        let mut grid = rolls.grid.clone();
+
+        // Repeat until we reach a stable state
+        loop {
+            let n_removed = remove_rolls(&mut grid);
+            if n_removed == 0 {
+                break;
+            }
+            total_removed += n_removed;
         }
-        total_removed += n_removed;
+        Ok(total_removed.into())
     }
-    ctx.set_sol2(total_removed);
 }
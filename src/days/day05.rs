@@ -1,54 +1,80 @@
-use aoc::Context;
-
-pub fn solve(ctx: &mut Context) {
-    let mut ranges = Vec::new();
-    let mut ids = Vec::new();
-    for line in ctx.input() {
-        if line.is_empty() {
-            continue;
+use crate::{Context, Error, Result, Solution, Solver, parse_field};
+
+pub struct Inventory {
+    ranges: Vec<(u64, u64)>,
+    ids: Vec<u64>,
+}
+
+pub struct Day05;
+
+impl Solver for Day05 {
+    type Parsed = Inventory;
+
+    const TITLE: &'static str = "Cafeteria";
+
+    fn parse(&self, input: &[String]) -> Result<Self::Parsed> {
+        let mut ranges = Vec::new();
+        let mut ids = Vec::new();
+        for (index, line) in input.iter().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut split = line.split('-');
+            let first_num = parse_field::<u64>(index, line, split.next().unwrap())?;
+            if let Some(second_str) = split.next() {
+                let second_num = parse_field::<u64>(index, line, second_str)?;
+                ranges.push((first_num, second_num));
+            } else {
+                ids.push(first_num);
+            }
         }
 
-        let mut split = line.split('-');
-        let first_num = split.next().unwrap().parse::<u64>().unwrap();
-        if let Some(second_str) = split.next() {
-            let second_num = second_str.parse::<u64>().unwrap();
-            ranges.push((first_num, second_num));
-        } else {
-            ids.push(first_num);
+        if ranges.is_empty() {
+            return Err(Error::missing_section("fresh ingredient ID ranges"));
         }
+        if ids.is_empty() {
+            return Err(Error::missing_section("available ingredient IDs"));
+        }
+        Ok(Inventory { ranges, ids })
     }
 
-    let mut result = 0;
-    for id in &ids {
-        for (start, end) in &ranges {
-            if id >= start && id <= end {
-                result += 1;
-                break;
+    fn part1(&self, inventory: &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        let mut result = 0;
+        for id in &inventory.ids {
+            for (start, end) in &inventory.ranges {
+                if id >= start && id <= end {
+                    result += 1;
+                    break;
+                }
             }
         }
+
+        Ok(result.into())
     }
 
-    ctx.set_sol1(result);
-
-    // Sort ascending by start value
-    ranges.sort_by(|a, b| a.0.cmp(&b.0));
-    let mut merged_ranges = Vec::new();
-    let mut current_range = ranges[0];
-    ranges.iter().skip(1).for_each(|range| {
-        if range.0 <= current_range.1 {
-            current_range = (current_range.0, current_range.1.max(range.1));
-        } else {
-            // No overlap, push the current range and move to the next
-            merged_ranges.push(current_range);
-            current_range = *range;
+    fn part2(&self, inventory: &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        // Sort ascending by start value
+        let mut ranges = inventory.ranges.clone();
+        ranges.sort_by_key(|r| r.0);
+        let mut merged_ranges = Vec::new();
+        let mut current_range = ranges[0];
+        ranges.iter().skip(1).for_each(|range| {
+            if range.0 <= current_range.1 {
+                current_range = (current_range.0, current_range.1.max(range.1));
+            } else {
+                // No overlap, push the current range and move to the next
+                merged_ranges.push(current_range);
+                current_range = *range;
+            }
+        });
+        merged_ranges.push(current_range);
+
+        let mut result2: u64 = 0;
+        for (start, end) in &merged_ranges {
+            result2 += end - start + 1;
         }
-    });
-    merged_ranges.push(current_range);
 
-    let mut result2 = 0;
-    for (start, end) in &merged_ranges {
-        result2 += end - start + 1;
+        Ok(result2.into())
     }
-
-    ctx.set_sol2(result2);
 }
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use aoc::Context;
+use crate::{Context, Error, Result, Solution, Solver};
 
 #[derive(Default)]
 struct Operation {
@@ -8,42 +8,90 @@ struct Operation {
     operator: char,
 }
 
-pub fn solve(ctx: &mut Context) {
-    let mut operations = HashMap::new();
-    for line in ctx.input() {
-        line.split_whitespace()
-            .enumerate()
-            .for_each(|(index, data)| {
-                let operation = operations.entry(index).or_insert(Operation {
-                    operands: Vec::new(),
-                    operator: ' ',
-                });
-                if let Ok(value) = data.parse::<u64>() {
-                    operation.operands.push(value);
-                } else if data == "+" || data == "*" {
-                    operation.operator = data.chars().next().unwrap();
-                }
-            });
-    }
-
-    let mut result1: u64 = 0;
-    for op in operations.values() {
+fn total(operations: &[Operation]) -> u64 {
+    let mut result: u64 = 0;
+    for op in operations {
         let res = match op.operator {
             '+' => op.operands.iter().sum(),
             '*' => op.operands.iter().product(),
             _ => 0,
         };
-        result1 += res;
+        result += res;
+    }
+    result
+}
+
+// Both readings of the worksheet: numbers written in rows, then numbers
+// written in columns
+pub struct Worksheet {
+    rows: Vec<Operation>,
+    columns: Vec<Operation>,
+}
+
+pub struct Day06;
+
+impl Solver for Day06 {
+    type Parsed = Worksheet;
+
+    const TITLE: &'static str = "Trash Compactor";
+
+    fn parse(&self, input: &[String]) -> Result<Self::Parsed> {
+        Ok(Worksheet {
+            rows: parse_rows(input)?,
+            columns: parse_columns(input)?,
+        })
+    }
+
+    fn part1(&self, worksheet: &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        Ok(total(&worksheet.rows).into())
+    }
+
+    fn part2(&self, worksheet: &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        Ok(total(&worksheet.columns).into())
     }
-    ctx.set_sol1(result1);
+}
 
+fn parse_rows(input: &[String]) -> Result<Vec<Operation>> {
+    // At least one line of numbers and the line of operators
+    if input.len() < 2 {
+        return Err(Error::missing_section("operators"));
+    }
+
+    let mut operations = HashMap::new();
+    for (line_index, line) in input.iter().enumerate() {
+        for (index, data) in line.split_whitespace().enumerate() {
+            let operation = operations.entry(index).or_insert(Operation {
+                operands: Vec::new(),
+                operator: ' ',
+            });
+            if let Ok(value) = data.parse::<u64>() {
+                operation.operands.push(value);
+            } else if data == "+" || data == "*" {
+                operation.operator = data.chars().next().unwrap();
+            } else {
+                return Err(Error::at(
+                    line_index,
+                    line,
+                    data,
+                    format!("expected a number or an operator, found {:?}", data),
+                ));
+            }
+        }
+    }
+
+    let mut operations: Vec<_> = operations.into_iter().collect();
+    operations.sort_by_key(|(index, _)| *index);
+    Ok(operations.into_iter().map(|(_, op)| op).collect())
+}
+
+fn parse_columns(input: &[String]) -> Result<Vec<Operation>> {
     let mut operations = vec![Operation::default()];
-    let max_cols = ctx.input().iter().map(|l| l.len()).max().unwrap();
-    let last_digit_line_index = ctx.input().len() - 2;
+    let max_cols = input.iter().map(|l| l.len()).max().unwrap_or(0);
+    let last_digit_line_index = input.len() - 2;
     let mut operation_n = 0;
     for col in 0..max_cols {
         let mut operand = "".to_string();
-        for line in ctx.input().iter().take(last_digit_line_index + 1) {
+        for line in input.iter().take(last_digit_line_index + 1) {
             operand.push(line.chars().nth(col).unwrap_or(' '));
         }
         if operand.trim().is_empty() {
@@ -52,33 +100,38 @@ pub fn solve(ctx: &mut Context) {
             continue;
         }
         let operation = &mut operations[operation_n];
-        operation
-            .operands
-            .push(operand.trim().parse::<u64>().unwrap());
+        let value = operand.trim().parse::<u64>().map_err(|_| {
+            Error::parse(
+                1,
+                col + 1,
+                format!("expected a vertical number, found {:?}", operand.trim()),
+            )
+        })?;
+        operation.operands.push(value);
     }
 
-    ctx.input()
-        .last()
-        .unwrap()
-        .split_whitespace()
-        .enumerate()
-        .for_each(|(index, data)| {
-            let operation = &mut operations[index];
-            if data == "+" || data == "*" {
-                operation.operator = data.chars().next().unwrap();
-            } else {
-                panic!("Unexpected data in last line: {}", data);
-            }
-        });
-
-    let mut result2: u64 = 0;
-    for op in &operations {
-        let res = match op.operator {
-            '+' => op.operands.iter().sum(),
-            '*' => op.operands.iter().product(),
-            _ => 0,
+    let last_index = input.len() - 1;
+    let last_line = &input[last_index];
+    for (index, data) in last_line.split_whitespace().enumerate() {
+        let Some(operation) = operations.get_mut(index) else {
+            return Err(Error::at(
+                last_index,
+                last_line,
+                data,
+                "more operators than columns of numbers",
+            ));
         };
-        result2 += res;
+        if data == "+" || data == "*" {
+            operation.operator = data.chars().next().unwrap();
+        } else {
+            return Err(Error::at(
+                last_index,
+                last_line,
+                data,
+                format!("expected an operator, found {:?}", data),
+            ));
+        }
     }
-    ctx.set_sol2(result2);
+
+    Ok(operations)
 }
@@ -1,13 +1,21 @@
-use aoc::Context;
-use aoc::Grid;
-use aoc::Point;
+use crate::Grid;
+use crate::Point;
+use crate::{Context, Error, Result, Solution, Solver};
 
 use std::collections::HashMap;
 use std::collections::VecDeque;
 
-pub fn solve1(ctx: &mut Context) {
-    let mut map = Grid::parse(ctx.input());
-    let start = map.find(b'S').unwrap();
+fn parse(input: &[String]) -> Result<(Grid<u8>, Point)> {
+    let map = Grid::try_parse(input)?;
+    let start = map
+        .find(b'S')
+        .ok_or_else(|| Error::missing_section("start position 'S'"))?;
+    Ok((map, start))
+}
+
+pub fn solve1(map: &Grid<u8>, start: Point) -> Result<u32> {
+    // Beams are drawn onto the map as they go
+    let mut map = map.clone();
 
     let mut queue = VecDeque::new();
     queue.push_front(start);
@@ -33,11 +41,17 @@ pub fn solve1(ctx: &mut Context) {
                 queue.push_front(pos.right(1));
             }
             b'|' => {}
-            _ => unreachable!(),
+            other => {
+                return Err(Error::parse(
+                    pos.y as usize + 1,
+                    pos.x as usize + 1,
+                    format!("unexpected character {:?}", other as char),
+                ));
+            }
         }
     }
 
-    ctx.set_sol1(num_splits);
+    Ok(num_splits)
 }
 
 pub fn solve2_recursion(map: &Grid<u8>, start: &Point, cache: &mut HashMap<Point, u64>) -> u64 {
@@ -65,15 +79,27 @@ pub fn solve2_recursion(map: &Grid<u8>, start: &Point, cache: &mut HashMap<Point
     result
 }
 
-pub fn solve2(ctx: &mut Context) {
-    let map = Grid::parse(ctx.input());
-    let start = map.find(b'S').unwrap();
+pub fn solve2(map: &Grid<u8>, start: Point) -> u64 {
     let mut cache = HashMap::new();
-    let num_paths = solve2_recursion(&map, &start, &mut cache);
-    ctx.set_sol2(num_paths);
+    solve2_recursion(map, &start, &mut cache)
 }
 
-pub fn solve(ctx: &mut Context) {
-    solve1(ctx);
-    solve2(ctx);
+pub struct Day07;
+
+impl Solver for Day07 {
+    type Parsed = (Grid<u8>, Point);
+
+    const TITLE: &'static str = "Laboratories";
+
+    fn parse(&self, input: &[String]) -> Result<Self::Parsed> {
+        parse(input)
+    }
+
+    fn part1(&self, (map, start): &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        Ok(solve1(map, *start)?.into())
+    }
+
+    fn part2(&self, (map, start): &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        Ok(solve2(map, *start).into())
+    }
 }
@@ -1,4 +1,4 @@
-use aoc::Context;
+use crate::{Context, Error, Result, Solution, Solver, parse_field, require_field};
 
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -24,67 +24,112 @@ struct Graph {
     nodes: HashMap<usize, HashSet<usize>>, // node index -> list of neighbor indices
 }
 
-pub fn solve(ctx: &mut Context) {
-    let mut coords = Vec::new();
-    for line in ctx.input() {
-        let mut split = line.split(',');
-        coords.push(Coordinate {
-            x: split.next().unwrap().parse::<i64>().unwrap(),
-            y: split.next().unwrap().parse::<i64>().unwrap(),
-            z: split.next().unwrap().parse::<i64>().unwrap(),
+// Junction boxes along with every pair of them, closest pairs first
+pub struct Playground {
+    coords: Vec<Coordinate>,
+    sorted_distances: Vec<((usize, usize), f64)>,
+}
+
+impl Playground {
+    fn empty_graph(&self) -> Graph {
+        let mut graph = Graph {
+            nodes: HashMap::new(),
+        };
+        self.coords.iter().enumerate().for_each(|(i, _)| {
+            graph.nodes.insert(i, HashSet::new());
         });
+        graph
     }
+}
 
-    let mut distances: HashMap<(usize, usize), f64> = HashMap::new(); // (index1, index2) -> distance
-    for i in 0..coords.len() {
-        for j in (i + 1)..coords.len() {
-            let dist = coords[i].euclidean_distance(&coords[j]);
-            distances.insert((i, j), dist);
+pub struct Day08;
+
+impl Solver for Day08 {
+    type Parsed = Playground;
+
+    const TITLE: &'static str = "Playground";
+
+    fn parse(&self, input: &[String]) -> Result<Self::Parsed> {
+        let mut coords = Vec::new();
+        for (index, line) in input.iter().enumerate() {
+            let mut split = line.split(',');
+            let mut next = |what| {
+                parse_field::<i64>(index, line, require_field(index, line, split.next(), what)?)
+            };
+            coords.push(Coordinate {
+                x: next("x coordinate")?,
+                y: next("y coordinate")?,
+                z: next("z coordinate")?,
+            });
+        }
+        if coords.len() < 2 {
+            return Err(Error::no_solution("at least two junction boxes are needed"));
         }
-    }
 
-    // Order by distances ascending
-    let mut sorted_distances: Vec<((usize, usize), f64)> = distances.into_iter().collect();
-    sorted_distances.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
-
-    let mut graph = Graph {
-        nodes: HashMap::new(),
-    };
-    coords.iter().enumerate().for_each(|(i, _)| {
-        graph.nodes.insert(i, HashSet::new());
-    });
-
-    let mut problem_1_graph = graph.clone();
-    let n = if ctx.is_example() { 10 } else { 1000 };
-    connect_n_first_pairs(n, &sorted_distances, &mut problem_1_graph);
-
-    let mut circuits = calculate_circuits(&problem_1_graph);
-    // Order circuits by size descending
-    circuits.sort_by_key(|b| std::cmp::Reverse(b.len()));
-    let result = circuits.iter().take(3).map(|c| c.len()).product::<usize>();
-    ctx.set_sol1(result);
-
-    // Find the minimum n that results in a single circuit
-    let mut low = 1;
-    let mut high = sorted_distances.len();
-    while low < high {
-        let mid = (low + high) / 2;
-        let mut test_graph = graph.clone();
-        connect_n_first_pairs(mid, &sorted_distances, &mut test_graph);
-        let test_circuits = calculate_circuits(&test_graph);
-        if test_circuits.len() == 1 {
-            high = mid;
-        } else {
-            low = mid + 1;
+        let mut distances: HashMap<(usize, usize), f64> = HashMap::new(); // (index1, index2) -> distance
+        for i in 0..coords.len() {
+            for j in (i + 1)..coords.len() {
+                let dist = coords[i].euclidean_distance(&coords[j]);
+                distances.insert((i, j), dist);
+            }
         }
+
+        // Order by distances ascending
+        let mut sorted_distances: Vec<((usize, usize), f64)> = distances.into_iter().collect();
+        sorted_distances.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+
+        Ok(Playground {
+            coords,
+            sorted_distances,
+        })
     }
 
-    // The pair that connects the last two circuits is at index low - 1
-    let pair_index = low - 1;
-    let (box1, box2) = sorted_distances[pair_index].0;
-    let box1 = &coords[box1];
-    let box2 = &coords[box2];
-    ctx.set_sol2(box1.x * box2.x);
+    fn part1(&self, playground: &Self::Parsed, ctx: &Context) -> Result<Solution> {
+        let mut problem_1_graph = playground.empty_graph();
+        let n = ctx.param("connections", 1000, 10)?;
+        connect_n_first_pairs(n, &playground.sorted_distances, &mut problem_1_graph);
+
+        let mut circuits = calculate_circuits(&problem_1_graph);
+        // Order circuits by size descending
+        circuits.sort_by_key(|b| std::cmp::Reverse(b.len()));
+        ctx.debug(
+            "circuits connected",
+            &[("connections", &n), ("circuits", &circuits.len())],
+        );
+        let result = circuits.iter().take(3).map(|c| c.len()).product::<usize>();
+        Ok(result.into())
+    }
+
+    fn part2(&self, playground: &Self::Parsed, ctx: &Context) -> Result<Solution> {
+        let sorted_distances = &playground.sorted_distances;
+        let graph = playground.empty_graph();
+
+        // Find the minimum n that results in a single circuit
+        let mut low = 1;
+        let mut high = sorted_distances.len();
+        while low < high {
+            if ctx.should_stop() {
+                return Err(Error::Timeout);
+            }
+            let mid = (low + high) / 2;
+            let mut test_graph = graph.clone();
+            connect_n_first_pairs(mid, sorted_distances, &mut test_graph);
+            let test_circuits = calculate_circuits(&test_graph);
+            if test_circuits.len() == 1 {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+
+        // The pair that connects the last two circuits is at index low - 1
+        let pair_index = low - 1;
+        ctx.debug("single circuit", &[("connections", &low)]);
+        let (box1, box2) = sorted_distances[pair_index].0;
+        let box1 = &playground.coords[box1];
+        let box2 = &playground.coords[box2];
+        Ok((box1.x * box2.x).into())
+    }
 }
 
 fn connect_n_first_pairs(n: usize, sorted_distances: &[((usize, usize), f64)], graph: &mut Graph) {
@@ -1,5 +1,7 @@
-use aoc::Context;
-use aoc::Point;
+use crate::{Context, Error, Result, Solution, Solver, parse_field, require_field};
+use crate::{DaySolver, Grid, ORTHOGONALS, Point};
+
+use std::collections::VecDeque;
 
 fn solve1(red_tiles: &[Point]) -> i64 {
     let mut max_area = 0;
@@ -44,8 +46,9 @@ fn edges_intersect(e1: &Edge, e2: &Edge) -> EdgesRelation {
     EdgesRelation::Intersect(t > 0.0 && t < 1.0 && u > 0.0 && u < 1.0)
 }
 
-fn solve2(red_tiles: &[Point]) -> i64 {
+fn solve2(red_tiles: &[Point], ctx: &Context) -> i64 {
     // Build edges
+    let span = ctx.span("build edges");
     let mut edges: Vec<Edge> = Vec::new();
     for i in 0..(red_tiles.len() - 1) {
         let p1 = red_tiles[i];
@@ -56,8 +59,10 @@ fn solve2(red_tiles: &[Point]) -> i64 {
     let p1 = red_tiles[red_tiles.len() - 1];
     let p2 = red_tiles[0];
     edges.push(Edge { p1, p2 });
+    drop(span);
 
     // Find max area achieved by any pair of points that is fully enclosed by edges
+    let _span = ctx.span("check rectangles");
     let mut max_area = 0;
     for i in 0..red_tiles.len() {
         for j in (i + 1)..red_tiles.len() {
@@ -103,6 +108,7 @@ fn solve2(red_tiles: &[Point]) -> i64 {
                     p2: Point::new(box1.x, box1.y),
                 },
             ];
+            let span = ctx.span("intersect edges");
             'outer: for re in &rect_edges {
                 for e in &edges {
                     match edges_intersect(re, e) {
@@ -116,6 +122,8 @@ fn solve2(red_tiles: &[Point]) -> i64 {
                 }
             }
 
+            drop(span);
+
             if !intersects && area > max_area {
                 max_area = area;
             }
@@ -125,18 +133,162 @@ fn solve2(red_tiles: &[Point]) -> i64 {
     max_area
 }
 
-pub fn solve(ctx: &mut Context) {
-    let red_tiles: Vec<Point> = ctx
-        .input()
-        .iter()
-        .map(|l| {
-            let mut split = l.split(',');
-            let x = split.next().unwrap().trim().parse::<i64>().unwrap();
-            let y = split.next().unwrap().trim().parse::<i64>().unwrap();
-            Point::new(x, y)
-        })
-        .collect();
-
-    ctx.set_sol1(solve1(&red_tiles));
-    ctx.set_sol2(solve2(&red_tiles));
+pub struct Day09;
+
+impl Solver for Day09 {
+    type Parsed = Vec<Point>;
+
+    const TITLE: &'static str = "Movie Theater";
+
+    const VARIANT: &'static str = "naive";
+    const ALTERNATIVES: &'static [&'static dyn DaySolver] = &[&Day09Compressed];
+
+    fn parse(&self, input: &[String]) -> Result<Self::Parsed> {
+        let red_tiles: Vec<Point> = input
+            .iter()
+            .enumerate()
+            .map(|(index, l)| {
+                let mut split = l.split(',');
+                let x = require_field(index, l, split.next(), "x coordinate")?;
+                let y = require_field(index, l, split.next(), "y coordinate")?;
+                Ok(Point::new(
+                    parse_field(index, l, x.trim())?,
+                    parse_field(index, l, y.trim())?,
+                ))
+            })
+            .collect::<Result<_>>()?;
+        if red_tiles.is_empty() {
+            return Err(Error::missing_section("red tiles"));
+        }
+        Ok(red_tiles)
+    }
+
+    fn part1(&self, red_tiles: &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        Ok(solve1(red_tiles).into())
+    }
+
+    fn part2(&self, red_tiles: &Self::Parsed, ctx: &Context) -> Result<Solution> {
+        Ok(solve2(red_tiles, ctx).into())
+    }
+}
+
+// Same puzzle on a grid with one row and one column per distinct coordinate of the
+// red tiles, with a row or column in between for the tiles that separate them.
+// Every rectangle is then checked in constant time with prefix sums.
+pub struct Day09Compressed;
+
+impl Solver for Day09Compressed {
+    type Parsed = Vec<Point>;
+
+    const TITLE: &'static str = Day09::TITLE;
+
+    const VARIANT: &'static str = "compressed";
+
+    fn parse(&self, input: &[String]) -> Result<Self::Parsed> {
+        Day09.parse(input)
+    }
+
+    fn part1(&self, red_tiles: &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        Ok(solve1(red_tiles).into())
+    }
+
+    fn part2(&self, red_tiles: &Self::Parsed, ctx: &Context) -> Result<Solution> {
+        Ok(solve2_compressed(red_tiles, ctx)?.into())
+    }
+}
+
+// Sorted distinct values, so that `values[i]` is found at `2 * i + 1` in the grid.
+// Index 0 and the last index are padding outside of the loop.
+fn compress(values: impl Iterator<Item = i64>) -> Vec<i64> {
+    let mut values: Vec<i64> = values.collect();
+    values.sort_unstable();
+    values.dedup();
+    values
+}
+
+fn compressed_index(values: &[i64], value: i64) -> i64 {
+    2 * values.binary_search(&value).unwrap() as i64 + 1
+}
+
+fn solve2_compressed(red_tiles: &[Point], ctx: &Context) -> Result<i64> {
+    let xs = compress(red_tiles.iter().map(|p| p.x));
+    let ys = compress(red_tiles.iter().map(|p| p.y));
+    let to_grid = |p: &Point| Point::new(compressed_index(&xs, p.x), compressed_index(&ys, p.y));
+
+    // Draw the loop of red and green tiles
+    let span = ctx.span("draw loop");
+    let mut map = Grid::new(2 * xs.len() + 1, 2 * ys.len() + 1, b'.');
+    for (i, tile) in red_tiles.iter().enumerate() {
+        let next = &red_tiles[(i + 1) % red_tiles.len()];
+        if tile.x != next.x && tile.y != next.y {
+            return Err(Error::no_solution(format!(
+                "red tiles {},{} and {},{} are not on the same row or column",
+                tile.x, tile.y, next.x, next.y
+            )));
+        }
+        let (from, to) = (to_grid(tile), to_grid(next));
+        let step = Point::new((to.x - from.x).signum(), (to.y - from.y).signum());
+        let mut pos = from;
+        map[pos] = b'#';
+        while pos != to {
+            pos += step;
+            map[pos] = b'#';
+        }
+    }
+    drop(span);
+
+    // Everything that can be reached from the padding is outside of the loop
+    let span = ctx.span("fill outside");
+    let mut queue = VecDeque::from([Point::new(0, 0)]);
+    map[Point::new(0, 0)] = b' ';
+    while let Some(pos) = queue.pop_front() {
+        for direction in ORTHOGONALS {
+            let next = pos + direction;
+            if map.get(next) == Some(&b'.') {
+                map[next] = b' ';
+                queue.push_back(next);
+            }
+        }
+    }
+    drop(span);
+
+    // outside[y][x] is the number of outside cells above and to the left of (x, y)
+    let (width, height) = (2 * xs.len() + 1, 2 * ys.len() + 1);
+    let mut outside = vec![vec![0u32; width + 1]; height + 1];
+    for y in 0..height {
+        for x in 0..width {
+            let cell = u32::from(map[Point::new(x as i64, y as i64)] == b' ');
+            outside[y + 1][x + 1] = cell + outside[y][x + 1] + outside[y + 1][x] - outside[y][x];
+        }
+    }
+
+    let _span = ctx.span("check rectangles");
+    let mut max_area = 0;
+    for i in 0..red_tiles.len() {
+        for j in (i + 1)..red_tiles.len() {
+            let (box1, box2) = (&red_tiles[i], &red_tiles[j]);
+            let area = ((box1.x - box2.x).abs() + 1) * ((box1.y - box2.y).abs() + 1);
+            if area <= max_area {
+                continue;
+            }
+
+            let (corner1, corner2) = (to_grid(box1), to_grid(box2));
+            let (x1, x2) = (
+                corner1.x.min(corner2.x) as usize,
+                corner1.x.max(corner2.x) as usize,
+            );
+            let (y1, y2) = (
+                corner1.y.min(corner2.y) as usize,
+                corner1.y.max(corner2.y) as usize,
+            );
+            let outside_cells = outside[y2 + 1][x2 + 1] + outside[y1][x1]
+                - outside[y1][x2 + 1]
+                - outside[y2 + 1][x1];
+            if outside_cells == 0 {
+                max_area = area;
+            }
+        }
+    }
+
+    Ok(max_area)
 }
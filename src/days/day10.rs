@@ -1,71 +1,131 @@
-use good_lp::{Expression, Solution, SolverModel, Variable, microlp, variable, variables};
+use good_lp::{Expression, Solution as _, SolverModel, Variable, microlp, variable, variables};
 use std::collections::{HashSet, VecDeque};
 
-use aoc::Context;
+use crate::{Context, Error, Result, Solution, Solver, parse_field};
 
 #[derive(Clone, Debug)]
 struct Button {
     connections: Vec<usize>, // indices of connected indicator lights
 }
 
-struct Problem {
+pub struct Problem {
     buttons: Vec<Button>,
     indicator_lights: Vec<bool>,
     goal_states: Vec<bool>,
     joltages_goals: Vec<u64>,
 }
 
-pub fn solve(ctx: &mut Context) {
-    // One problem per line
-    let mut problems = Vec::new();
-    ctx.input().iter().for_each(|line| {
-        let parts: Vec<&str> = line.split(' ').collect();
-        let goal_states: Vec<bool> = parts[0]
-            .trim_start_matches('[')
-            .trim_end_matches(']')
-            .chars()
-            .map(|c| c == '#')
-            .collect();
-        let mut buttons = Vec::new();
-        for part in &parts[1..(parts.len() - 1)] {
-            let conn_str = part.trim_start_matches('(').trim_end_matches(')');
-            let connections = conn_str
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Parsed = Vec<Problem>;
+
+    const TITLE: &'static str = "Factory";
+
+    fn parse(&self, input: &[String]) -> Result<Self::Parsed> {
+        // One problem per line
+        let mut problems = Vec::new();
+        for (index, line) in input.iter().enumerate() {
+            let parts: Vec<&str> = line.split(' ').collect();
+            if parts.len() < 2 {
+                return Err(Error::at(
+                    index,
+                    line,
+                    &line[line.len()..],
+                    "expected indicator lights, buttons and joltages",
+                ));
+            }
+            let lights = parts[0];
+            let Some(lights_str) = lights.strip_prefix('[').and_then(|l| l.strip_suffix(']'))
+            else {
+                return Err(Error::at(
+                    index,
+                    line,
+                    lights,
+                    "expected [indicator lights]",
+                ));
+            };
+            let goal_states: Vec<bool> = lights_str.chars().map(|c| c == '#').collect();
+            let mut buttons = Vec::new();
+            for part in &parts[1..(parts.len() - 1)] {
+                let conn_str = part.trim_start_matches('(').trim_end_matches(')');
+                let connections = conn_str
+                    .split(',')
+                    .map(|s| {
+                        let light = parse_field::<usize>(index, line, s)?;
+                        if light >= goal_states.len() {
+                            return Err(Error::at(
+                                index,
+                                line,
+                                s,
+                                format!("there are only {} indicator lights", goal_states.len()),
+                            ));
+                        }
+                        Ok(light)
+                    })
+                    .collect::<Result<_>>()?;
+                buttons.push(Button { connections });
+            }
+            let joltage_part = parts[parts.len() - 1];
+            let joltage_str = joltage_part.trim_start_matches('{').trim_end_matches('}');
+            let joltages_goals: Vec<u64> = joltage_str
                 .split(',')
-                .map(|s| s.parse::<usize>().unwrap())
-                .collect();
-            buttons.push(Button { connections });
+                .map(|s| parse_field(index, line, s))
+                .collect::<Result<_>>()?;
+            if goal_states.len() != joltages_goals.len() {
+                return Err(Error::at(
+                    index,
+                    line,
+                    joltage_part,
+                    format!(
+                        "expected {} joltages, found {}",
+                        goal_states.len(),
+                        joltages_goals.len()
+                    ),
+                ));
+            }
+            problems.push(Problem {
+                buttons,
+                indicator_lights: vec![false; goal_states.len()],
+                goal_states,
+                joltages_goals,
+            });
         }
-        let joltage_str = parts[parts.len() - 1]
-            .trim_start_matches('{')
-            .trim_end_matches('}');
-        let joltages_goals: Vec<u64> = joltage_str
-            .split(',')
-            .map(|s| s.parse::<u64>().unwrap())
-            .collect();
-        assert!(goal_states.len() == joltages_goals.len());
-        problems.push(Problem {
-            buttons,
-            indicator_lights: vec![false; goal_states.len()],
-            goal_states,
-            joltages_goals,
-        });
-    });
+        Ok(problems)
+    }
 
-    let result1: usize = problems.iter().map(solve_problem1).sum();
-    ctx.set_sol1(result1);
+    fn part1(&self, problems: &Self::Parsed, ctx: &Context) -> Result<Solution> {
+        let result1: usize = problems
+            .iter()
+            .map(|problem| solve_problem1(problem, ctx))
+            .sum::<Result<_>>()?;
+        Ok(result1.into())
+    }
 
-    let result2: usize = problems.iter().map(solve_problem2).sum();
-    ctx.set_sol2(result2);
+    fn part2(&self, problems: &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        let result2: usize = problems.iter().map(solve_problem2).sum::<Result<_>>()?;
+        Ok(result2.into())
+    }
 }
 
-fn solve_problem1(problem: &Problem) -> usize {
+fn solve_problem1(problem: &Problem, ctx: &Context) -> Result<usize> {
     // BFS over button presses to find the minimum press sequence to reach the goal state
     let mut queue = VecDeque::new();
     let mut visited = HashSet::new();
     queue.push_back((problem.indicator_lights.clone(), Vec::new()));
     while let Some((current_state, press_sequence)) = queue.pop_front() {
+        if ctx.should_stop() {
+            return Err(Error::Timeout);
+        }
         if current_state == problem.goal_states {
-            return press_sequence.len();
+            ctx.trace(
+                "indicator lights configured",
+                &[
+                    ("presses", &press_sequence.len()),
+                    ("visited", &visited.len()),
+                ],
+            );
+            return Ok(press_sequence.len());
         }
         for (button_idx, button) in problem.buttons.iter().enumerate() {
             // Apply button press
@@ -84,10 +144,12 @@ fn solve_problem1(problem: &Problem) -> usize {
         }
     }
 
-    unreachable!()
+    Err(Error::no_solution(
+        "no button sequence reaches the indicator light goal",
+    ))
 }
 
-fn solve_problem2(problem: &Problem) -> usize {
+fn solve_problem2(problem: &Problem) -> Result<usize> {
     // Problem 2 is a linear optimization problem. We need to find the minimum number of button presses
     // to reach the goal joltages, given that each button press increases the joltages in the same index by 1.
     // Suppose we have buttons A, B, C, D, E, F. And each button connects to the following joltage indicators:
@@ -149,6 +211,8 @@ fn solve_problem2(problem: &Problem) -> usize {
             let expr: Expression = connected_buttons.iter().map(|&idx| vars[idx]).sum();
             solver.add_constraint(expr.eq(goal as f64));
         });
-    let solution = solver.solve().unwrap();
-    solution.eval(&objective).round() as usize
+    let solution = solver
+        .solve()
+        .map_err(|err| Error::no_solution(format!("joltage goals can't be reached: {}", err)))?;
+    Ok(solution.eval(&objective).round() as usize)
 }
@@ -1,25 +1,38 @@
-use aoc::Context;
+use crate::{Context, Error, Result, Solution, Solver};
 use std::collections::HashMap;
 
-pub fn solve(ctx: &mut Context) {
-    let mut devices = HashMap::new();
-    for line in ctx.input() {
-        let parts: Vec<&str> = line.split(':').collect();
-        let device = parts[0].trim();
-        let connections: Vec<String> = parts[1].trim().split(' ').map(|s| s.to_string()).collect();
-        devices.insert(device.to_string(), connections);
+pub struct Day11;
+
+impl Solver for Day11 {
+    type Parsed = HashMap<String, Vec<String>>;
+
+    const TITLE: &'static str = "Reactor";
+
+    fn parse(&self, input: &[String]) -> Result<Self::Parsed> {
+        let mut devices = HashMap::new();
+        for (index, line) in input.iter().enumerate() {
+            let Some((device, outputs)) = line.split_once(':') else {
+                return Err(Error::at(index, line, &line[line.len()..], "expected ':'"));
+            };
+            let connections: Vec<String> =
+                outputs.trim().split(' ').map(|s| s.to_string()).collect();
+            devices.insert(device.trim().to_string(), connections);
+        }
+        Ok(devices)
     }
 
     // Note: examples for part 1 and 2 are different.
     // So the same run will not work for both parts with example input.
 
-    // Part one: paths from "you" to "out" (no required nodes)
-    let result = count_paths(&devices, "you", "out", false, false);
-    ctx.set_sol1(result);
+    fn part1(&self, devices: &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        // Paths from "you" to "out" (no required nodes)
+        Ok(count_paths(devices, "you", "out", false, false).into())
+    }
 
-    // Part two: paths from "svr" to "out" that pass through both "dac" and "fft"
-    let result2 = count_paths(&devices, "svr", "out", true, true);
-    ctx.set_sol2(result2);
+    fn part2(&self, devices: &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        // Paths from "svr" to "out" that pass through both "dac" and "fft"
+        Ok(count_paths(devices, "svr", "out", true, true).into())
+    }
 }
 
 struct DfsParams {
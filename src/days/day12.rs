@@ -1,72 +1,99 @@
-use aoc::Context;
+use crate::{Context, Error, Result, Solution, Solver, parse_field, require_field};
 
 // All gifts have a bounding box of 3x3 i.e. fit in a 9 area
 // Let's just count the area and see if it works
-struct GiftShape {
+pub struct GiftShape {
     area: u32,
 }
 
-struct Problem {
+pub struct Problem {
     width: u32,
     height: u32,
     number_of_gifts: Vec<u32>, // index correspond to type of GiftShape
 }
 
-pub fn solve(ctx: &mut Context) {
-    let mut gifts = Vec::new();
-    let mut problems = Vec::new();
-    let mut lines = ctx.input().iter();
-    while let Some(content) = lines.next() {
-        if content.is_empty() {
-            continue;
-        }
+pub struct Day12;
+
+impl Solver for Day12 {
+    type Parsed = (Vec<GiftShape>, Vec<Problem>);
+
+    const TITLE: &'static str = "Christmas Tree Farm";
+
+    // There is no second puzzle on the last day
+    const PARTS: u8 = 1;
+
+    fn parse(&self, input: &[String]) -> Result<Self::Parsed> {
+        let mut gifts = Vec::new();
+        let mut problems = Vec::new();
+        let mut lines = input.iter().enumerate();
+        while let Some((index, content)) = lines.next() {
+            if content.is_empty() {
+                continue;
+            }
 
-        if content.contains(':') {
-            // Can be 'N:' or 'AxB: 1 2 3 4 5'
-            let parts: Vec<&str> = content.split(':').collect();
-            if parts[0].contains('x') {
-                // AxB: ...
-                let mut dimensions = parts[0].split('x');
-                let width: u32 = dimensions.next().unwrap().parse().unwrap();
-                let height: u32 = dimensions.next().unwrap().parse().unwrap();
-                let numbers: Vec<u32> = parts[1]
-                    .split_whitespace()
-                    .map(|s| s.parse().unwrap())
-                    .collect();
-                problems.push(Problem {
-                    width,
-                    height,
-                    number_of_gifts: numbers,
-                });
-            } else {
-                // N: followed by shape description
-                // N itsel is irrelevant since it starts from 0 and they appear in order
-                // We know the gift shape is shown in the next 3 lines. so we just count the '#'
-                let mut area = 0;
-                for _ in 0..3 {
-                    let shape_line = lines.next().unwrap();
-                    area += shape_line.chars().filter(|&c| c == '#').count() as u32;
+            if let Some((head, tail)) = content.split_once(':') {
+                // Can be 'N:' or 'AxB: 1 2 3 4 5'
+                if head.contains('x') {
+                    // AxB: ...
+                    let mut dimensions = head.split('x');
+                    let width: u32 = parse_field(index, content, dimensions.next().unwrap())?;
+                    let height = require_field(index, content, dimensions.next(), "height")?;
+                    let height: u32 = parse_field(index, content, height)?;
+                    let numbers: Vec<u32> = tail
+                        .split_whitespace()
+                        .map(|s| parse_field(index, content, s))
+                        .collect::<Result<_>>()?;
+                    if numbers.len() > gifts.len() {
+                        return Err(Error::at(
+                            index,
+                            content,
+                            tail,
+                            format!("only {} gift shapes are defined", gifts.len()),
+                        ));
+                    }
+                    problems.push(Problem {
+                        width,
+                        height,
+                        number_of_gifts: numbers,
+                    });
+                } else {
+                    // N: followed by shape description
+                    // N itsel is irrelevant since it starts from 0 and they appear in order
+                    // We know the gift shape is shown in the next 3 lines. so we just count the '#'
+                    let mut area = 0;
+                    for _ in 0..3 {
+                        let Some((_, shape_line)) = lines.next() else {
+                            return Err(Error::missing_section(format!("shape {}", head)));
+                        };
+                        area += shape_line.chars().filter(|&c| c == '#').count() as u32;
+                    }
+                    gifts.push(GiftShape { area });
                 }
-                gifts.push(GiftShape { area });
             }
         }
-    }
 
-    let mut result = 0;
-    for problem in problems {
-        let total_area: u32 = problem
-            .number_of_gifts
-            .iter()
-            .enumerate()
-            .map(|(i, &count)| count * gifts[i].area)
-            .sum();
-        let box_area = problem.width * problem.height;
-        // Funny this works for the input but not for the example
-        // ¯\_(ツ)_/¯
-        if total_area <= box_area {
-            result += 1;
+        if problems.is_empty() {
+            return Err(Error::missing_section("regions"));
         }
+        Ok((gifts, problems))
     }
 
-    ctx.set_sol1(result);
+    fn part1(&self, (gifts, problems): &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        let mut result = 0;
+        for problem in problems {
+            let total_area: u32 = problem
+                .number_of_gifts
+                .iter()
+                .enumerate()
+                .map(|(i, &count)| count * gifts[i].area)
+                .sum();
+            let box_area = problem.width * problem.height;
+            // Funny this works for the input but not for the example
+            // ¯\_(ツ)_/¯
+            if total_area <= box_area {
+                result += 1;
+            }
+        }
+        Ok(result.into())
+    }
 }
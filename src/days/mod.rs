@@ -1,32 +1,40 @@
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
-pub mod day05;
-pub mod day06;
-pub mod day07;
-pub mod day08;
-pub mod day09;
-pub mod day10;
-pub mod day11;
-pub mod day12;
+// The `dayNN` modules, the `DAYS` registry and the `FINGERPRINTS` are generated by build.rs from
+// the files in this directory
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
 
-use aoc::Context;
+use crate::DaySolver;
 
-pub fn get_day_solver(day: u8) -> fn(&mut Context) -> () {
-    match day {
-        1 => day01::solve,
-        2 => day02::solve,
-        3 => day03::solve,
-        4 => day04::solve,
-        5 => day05::solve,
-        6 => day06::solve,
-        7 => day07::solve,
-        8 => day08::solve,
-        9 => day09::solve,
-        10 => day10::solve,
-        11 => day11::solve,
-        12 => day12::solve,
-        _ => unimplemented!(),
-    }
+pub fn get_day_solver(day: u8) -> Option<&'static dyn DaySolver> {
+    DAYS.iter()
+        .find(|(number, _)| *number == day)
+        .map(|&(_, solver)| solver)
+}
+
+// Hash of the code of `day`, see build.rs
+pub fn fingerprint(day: u8) -> Option<u64> {
+    FINGERPRINTS
+        .iter()
+        .find(|(number, _)| *number == day)
+        .map(|&(_, fingerprint)| fingerprint)
+}
+
+pub fn available_days() -> impl Iterator<Item = u8> {
+    DAYS.iter().map(|&(day, _)| day)
+}
+
+// Main solver of `day` followed by its alternative implementations
+pub fn get_variants(day: u8) -> Vec<&'static dyn DaySolver> {
+    get_day_solver(day)
+        .map(|solver| {
+            let mut variants = vec![solver];
+            variants.extend(solver.alternatives());
+            variants
+        })
+        .unwrap_or_default()
+}
+
+pub fn get_variant(day: u8, name: &str) -> Option<&'static dyn DaySolver> {
+    get_variants(day)
+        .into_iter()
+        .find(|solver| solver.variant() == name)
 }
@@ -0,0 +1,235 @@
+use aoc::InputSource;
+
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// The session cookie of the account the inputs belong to is read from the
+// environment, or from a file that is kept out of git with the rest of `.aoc/`
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const SESSION_PATH: &str = ".aoc/session";
+// Lets the downloads go to another server, e.g. a local stub
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const YEAR: u16 = 2025;
+// Advent of Code asks automated tools to say in their User-Agent who to contact
+// about them: an email address or a repository URL, `package.repository` of
+// Cargo.toml when set
+pub const CONTACT_VAR: &str = "AOC_CONTACT";
+const REPOSITORY: &str = env!("CARGO_PKG_REPOSITORY");
+
+// Requests are spaced by at least this much, including across runs, which is
+// why the time of the last one is kept on disk
+const MIN_INTERVAL: Duration = Duration::from_secs(3);
+const LAST_REQUEST_PATH: &str = ".aoc/last_fetch";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+// Where the input of a day is, or where it goes once downloaded
+enum LocalInput {
+    // Inputs are never downloaded twice
+    Present(PathBuf),
+    Missing(PathBuf),
+}
+
+struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+// Downloads the inputs of `days` that are missing, and returns how many failed.
+// Stops early when the server asks to slow down.
+pub fn fetch_days(days: &[u8]) -> usize {
+    let mut missing = Vec::new();
+    for &day in days {
+        match local_input(day) {
+            LocalInput::Present(path) => println!("Day {:02}: already in {}", day, path.display()),
+            LocalInput::Missing(path) => missing.push((day, path)),
+        }
+    }
+    if missing.is_empty() {
+        return 0;
+    }
+
+    let client = match Client::from_env() {
+        Ok(client) => client,
+        Err(message) => {
+            eprintln!("{}", message);
+            return missing.len();
+        }
+    };
+    let mut failures = 0;
+    for (index, (day, path)) in missing.iter().enumerate() {
+        match client
+            .download(*day)
+            .and_then(|input| write_atomically(path, &input).map_err(FetchError::from))
+        {
+            Ok(()) => println!("Day {:02}: downloaded to {}", day, path.display()),
+            Err(FetchError::RateLimited(message)) => {
+                eprintln!("Day {:02}: {}", day, message);
+                return failures + missing.len() - index;
+            }
+            Err(FetchError::Other(message)) => {
+                eprintln!("Day {:02}: {}", day, message);
+                failures += 1;
+            }
+        }
+    }
+    failures
+}
+
+enum FetchError {
+    RateLimited(String),
+    Other(String),
+}
+
+impl From<String> for FetchError {
+    fn from(message: String) -> Self {
+        FetchError::Other(message)
+    }
+}
+
+// Inputs are downloaded where the runner reads them from, see `InputSource::read`
+fn local_input(day: u8) -> LocalInput {
+    let mut candidates = InputSource::default_dir().candidate_paths(day);
+    // Empty files, e.g. from new-day, don't count as downloaded
+    match candidates
+        .iter()
+        .position(|path| fs::metadata(path).is_ok_and(|meta| meta.len() > 0))
+    {
+        Some(index) => LocalInput::Present(candidates.swap_remove(index)),
+        None => LocalInput::Missing(candidates.swap_remove(0)),
+    }
+}
+
+impl Client {
+    fn from_env() -> Result<Self, String> {
+        let base_url = env::var(BASE_URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        Ok(Client {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(&user_agent()?)
+                .timeout(TIMEOUT)
+                .build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session_token()?,
+        })
+    }
+
+    fn download(&self, day: u8) -> Result<String, FetchError> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, YEAR, day);
+        throttle()?;
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+        match response {
+            Ok(response) => {
+                let input = response
+                    .into_string()
+                    .map_err(|err| format!("Could not read the response of {}: {}", url, err))?;
+                if input.is_empty() {
+                    return Err(format!("{} returned an empty input", url).into());
+                }
+                Ok(input)
+            }
+            Err(ureq::Error::Status(429, response)) => {
+                let retry = response
+                    .header("Retry-After")
+                    .map_or(String::new(), |seconds| {
+                        format!(", retry after {} s", seconds)
+                    });
+                Err(FetchError::RateLimited(format!(
+                    "rate limited by {}{}",
+                    self.base_url, retry
+                )))
+            }
+            Err(ureq::Error::Status(404, _)) => {
+                Err(format!("no input at {}, the puzzle may not be unlocked yet", url).into())
+            }
+            Err(ureq::Error::Status(code @ (400 | 401 | 403), _)) => Err(format!(
+                "{} refused the session token (HTTP {}), it may have expired",
+                self.base_url, code
+            )
+            .into()),
+            Err(ureq::Error::Status(code, _)) => {
+                Err(format!("{} answered with HTTP {}", url, code).into())
+            }
+            Err(ureq::Error::Transport(err)) => {
+                Err(format!("Could not download {}: {}", url, err).into())
+            }
+        }
+    }
+}
+
+fn user_agent() -> Result<String, String> {
+    let contact = env::var(CONTACT_VAR)
+        .ok()
+        .filter(|contact| !contact.trim().is_empty())
+        .or_else(|| (!REPOSITORY.is_empty()).then(|| REPOSITORY.to_string()))
+        .ok_or_else(|| {
+            format!(
+                "No contact for the User-Agent: set {} to your email address or repository URL",
+                CONTACT_VAR
+            )
+        })?;
+    Ok(format!(
+        "{}/{} (+{}; puzzle input downloader)",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        contact.trim()
+    ))
+}
+
+fn session_token() -> Result<String, String> {
+    if let Ok(token) = env::var(SESSION_VAR)
+        && !token.trim().is_empty()
+    {
+        return Ok(token.trim().to_string());
+    }
+    match fs::read_to_string(SESSION_PATH) {
+        Ok(token) if !token.trim().is_empty() => Ok(token.trim().to_string()),
+        Ok(_) => Err(format!("{} is empty", SESSION_PATH)),
+        Err(err) if err.kind() == ErrorKind::NotFound => Err(format!(
+            "No session token: set {} or write it to {}",
+            SESSION_VAR, SESSION_PATH
+        )),
+        Err(err) => Err(format!("Could not read {}: {}", SESSION_PATH, err)),
+    }
+}
+
+// Waits until `MIN_INTERVAL` has passed since the previous request, then
+// records the new one
+fn throttle() -> Result<(), String> {
+    let now = || {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+    };
+    let last = fs::read_to_string(LAST_REQUEST_PATH)
+        .ok()
+        .and_then(|millis| millis.trim().parse().ok())
+        .map(Duration::from_millis);
+    if let Some(last) = last {
+        let wait = (last + MIN_INTERVAL).saturating_sub(now());
+        if !wait.is_zero() {
+            thread::sleep(wait);
+        }
+    }
+    write_atomically(Path::new(LAST_REQUEST_PATH), &now().as_millis().to_string())
+}
+
+// Goes through a temporary file, so that an interrupted download doesn't leave
+// a truncated input behind
+fn write_atomically(path: &Path, content: &str) -> Result<(), String> {
+    let error = |err: std::io::Error| format!("Could not write {}: {}", path.display(), err);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(error)?;
+    }
+    let partial = path.with_extension("part");
+    fs::write(&partial, content).map_err(error)?;
+    fs::rename(&partial, path).map_err(error)
+}
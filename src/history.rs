@@ -0,0 +1,222 @@
+use crate::report::{self, DayReport};
+
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Every run appends the answer and time of each solved part to this file
+pub const HISTORY_PATH: &str = ".aoc/history.csv";
+const HEADER: &str = "timestamp,commit,day,part,variant,example,answer,elapsed_ms,bench";
+// Times are compared with the median of this many previous runs of a part
+const WINDOW: usize = 5;
+
+pub struct Entry {
+    pub timestamp: u64,
+    pub commit: String,
+    pub day: u8,
+    pub part: u8,
+    pub variant: String,
+    pub example: bool,
+    pub answer: String,
+    pub elapsed_ms: f64,
+    // Whether `elapsed_ms` is the median of a benchmark rather than a single run
+    pub bench: bool,
+}
+
+// Answer or time of a part that got worse since the previous run
+pub enum Regression {
+    Answer { previous: String },
+    // Median of the previous runs
+    Time { previous_ms: f64 },
+}
+
+impl Entry {
+    // Whether both entries are about the same part, on the same kind of input
+    fn same_part(&self, other: &Entry) -> bool {
+        self.day == other.day
+            && self.part == other.part
+            && self.variant == other.variant
+            && self.example == other.example
+    }
+
+    // Whether the times of both entries can be compared, single runs being far
+    // noisier than benchmarks
+    fn same_timing(&self, other: &Entry) -> bool {
+        self.same_part(other) && self.bench == other.bench
+    }
+}
+
+// Entries for the solved parts of `report`
+pub fn entries(report: &DayReport, timestamp: u64, commit: &str) -> Vec<Entry> {
+    report
+        .parts
+        .iter()
+        .flatten()
+        .map(|sol| Entry {
+            timestamp,
+            commit: commit.to_string(),
+            day: report.day,
+            part: sol.part,
+            variant: report.variant.unwrap_or("default").to_string(),
+            example: report.is_example,
+            answer: sol.solution.to_string(),
+            elapsed_ms: sol.timing.elapsed_ms(),
+            bench: report.bench.is_some(),
+        })
+        .collect()
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs())
+}
+
+// Short hash of the checked out commit, if this is a git repository
+pub fn current_commit() -> String {
+    Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map_or(String::new(), |commit| commit.trim().to_string())
+}
+
+pub fn load(path: &Path) -> Result<Vec<Entry>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(format!("Could not read {}: {}", path.display(), err)),
+    };
+
+    let invalid = |index: usize| format!("{}:{}: invalid history entry", path.display(), index + 1);
+    content
+        .lines()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(index, line)| {
+            let mut fields = split_csv_line(line).into_iter();
+            let mut next = || fields.next().ok_or_else(|| invalid(index));
+            Ok(Entry {
+                timestamp: next()?.parse().map_err(|_| invalid(index))?,
+                commit: next()?,
+                day: next()?.parse().map_err(|_| invalid(index))?,
+                part: next()?.parse().map_err(|_| invalid(index))?,
+                variant: next()?,
+                example: next()?.parse().map_err(|_| invalid(index))?,
+                answer: next()?,
+                elapsed_ms: next()?.parse().map_err(|_| invalid(index))?,
+                // Missing from the entries written before it was recorded
+                bench: match next() {
+                    Ok(bench) => bench.parse().map_err(|_| invalid(index))?,
+                    Err(_) => false,
+                },
+            })
+        })
+        .collect()
+}
+
+pub fn append(path: &Path, entries: &[Entry]) -> Result<(), String> {
+    let error = |err: std::io::Error| format!("Could not write {}: {}", path.display(), err);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(error)?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(error)?;
+    let mut content = String::new();
+    if file.metadata().map_err(error)?.len() == 0 {
+        content.push_str(HEADER);
+        content.push('\n');
+    }
+    for entry in entries {
+        content.push_str(&format!(
+            "{},{},{},{},{},{},{},{:.6},{}\n",
+            entry.timestamp,
+            entry.commit,
+            entry.day,
+            entry.part,
+            report::csv_field(&entry.variant),
+            entry.example,
+            report::csv_field(&entry.answer),
+            entry.elapsed_ms,
+            entry.bench
+        ));
+    }
+    file.write_all(content.as_bytes()).map_err(error)
+}
+
+// Compares the answer of `entry` with the latest one of the same part in `history`,
+// and its time with the median of the previous runs timed the same way.
+// `threshold` is the relative slowdown that counts as a regression, e.g. 0.1 for 10%.
+pub fn regression(history: &[Entry], entry: &Entry, threshold: f64) -> Option<Regression> {
+    let previous = history.iter().rev().find(|e| e.same_part(entry))?;
+    if previous.answer != entry.answer {
+        return Some(Regression::Answer {
+            previous: previous.answer.clone(),
+        });
+    }
+    let previous_ms = median_ms(history, entry)?;
+    (entry.elapsed_ms > previous_ms * (1.0 + threshold)).then_some(Regression::Time { previous_ms })
+}
+
+// Median time of the last `WINDOW` runs of the same part as `entry`
+fn median_ms(history: &[Entry], entry: &Entry) -> Option<f64> {
+    let mut times: Vec<f64> = history
+        .iter()
+        .rev()
+        .filter(|e| e.same_timing(entry))
+        .take(WINDOW)
+        .map(|e| e.elapsed_ms)
+        .collect();
+    if times.is_empty() {
+        return None;
+    }
+    times.sort_by(f64::total_cmp);
+    let middle = times.len() / 2;
+    Some(match times.len() % 2 {
+        0 => (times[middle - 1] + times[middle]) / 2.0,
+        _ => times[middle],
+    })
+}
+
+// Total time of the parts of `entries` that were run before, along with the
+// total of the median times of their previous runs
+pub fn totals(history: &[Entry], entries: &[Entry]) -> Option<(f64, f64)> {
+    let pairs: Vec<(f64, f64)> = entries
+        .iter()
+        .filter_map(|entry| Some((entry.elapsed_ms, median_ms(history, entry)?)))
+        .collect();
+    (!pairs.is_empty()).then(|| {
+        pairs
+            .iter()
+            .fold((0.0, 0.0), |(now, before), (a, b)| (now + a, before + b))
+    })
+}
+
+// Fields of a line written with `report::csv_field`
+pub fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
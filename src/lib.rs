@@ -1,23 +1,26 @@
+pub mod days;
+
 pub mod util {
+  pub mod alloc;
+  pub mod error;
+  pub mod example;
   pub mod grid;
+  pub mod input;
   pub mod point;
+  pub mod profile;
   pub mod solution;
-}
-
-use std::fs::File;
-use std::io::{self, BufRead};
-
-pub fn read_lines(filepath: &str) -> io::Result<io::Lines<io::BufReader<File>>> {
-  let file = File::open(filepath)?;
-  Ok(io::BufReader::new(file).lines())
-}
-
-pub fn read_lines_as_vec(filepath: &str) -> io::Result<Vec<String>> {
-  let lines = read_lines(filepath)?;
-  Ok(lines.map_while(Result::ok).collect())
+  pub mod solver;
+  pub mod trace;
 }
 
 // Re-export modules for easy access
+pub use util::alloc::*;
+pub use util::error::*;
+pub use util::example;
 pub use util::grid::*;
+pub use util::input::*;
 pub use util::point::*;
+pub use util::profile::*;
 pub use util::solution::*;
+pub use util::solver::*;
+pub use util::trace::*;
@@ -1,76 +1,651 @@
-mod days;
-mod example;
+mod answers;
+mod bench;
+mod cache;
+mod cli;
+mod compare;
+mod fetch;
+mod history;
+mod panics;
+mod pool;
+mod report;
+mod scaffold;
+mod watch;
 
-use aoc::Context;
+use answers::{Answers, Verdict};
+use aoc::days;
+use aoc::{AllocStats, Context, DaySolver, Event, InputSource, Solution, SpanRecord, example};
+use bench::Stats;
+use cache::{Cache, CachedRun};
+use cli::Options;
+use history::Regression;
+use report::{DayReport, Format, PartReport, Timing};
+use std::collections::HashSet;
 use std::env;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Instant;
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        panic!("Please provide the day(s) to run as a command-line argument.");
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().is_some_and(|arg| arg == "new-day") {
+        new_day(&args[1..]);
+        return;
+    }
+    if args.first().is_some_and(|arg| arg == "fetch") {
+        fetch(&args[1..]);
+        return;
     }
 
-    let days: Vec<u8> = args[1..]
-        .iter()
-        .map(|x| {
-            x.parse()
-                .unwrap_or_else(|v| panic!("Not a valid day: {}", v))
-        })
-        .collect();
+    let options = match cli::parse_args(args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, cli::USAGE);
+            process::exit(2);
+        }
+    };
+
+    if options.list {
+        list_days();
+        return;
+    }
+
+    panics::install_hook();
+
+    if options.watch {
+        watch::watch(&options);
+    }
+
+    if options.compare {
+        let failures = compare::compare_days(&options);
+        if failures > 0 {
+            eprintln!(
+                "{} day(s) have implementations that fail or disagree",
+                failures
+            );
+            process::exit(1);
+        }
+        return;
+    }
 
     let mut runtime = 0.0;
+    let mut cached_days = 0;
+    let mut failures = 0;
+    let mut failed_days = Vec::new();
+    let mut used_params = HashSet::new();
+    let mut reports = Vec::new();
+    let timestamp = history::now();
+    let commit = history::current_commit();
+    let mut history_entries = Vec::new();
+
+    // Runs meant to measure, trace or compare the solvers always run them
+    let use_cache = !options.no_cache
+        && options.bench.is_none()
+        && !options.profile
+        && options.log_level.is_none()
+        && options.compare_last.is_none();
+    let cache_path = Path::new(cache::CACHE_PATH);
+    let cache = if use_cache {
+        Cache::load(cache_path).unwrap_or_else(|err| {
+            eprintln!("{}", err);
+            Cache::default()
+        })
+    } else {
+        Cache::default()
+    };
+    let mut cached_runs = Vec::new();
+
+    // Diagnostics go to the log file when there is one, next to the answers otherwise
+    let mut log_file = match &options.log_file {
+        Some(path) => match File::create(path) {
+            Ok(file) => Some(BufWriter::new(file)),
+            Err(err) => {
+                eprintln!("Could not create {}: {}", path.display(), err);
+                process::exit(2);
+            }
+        },
+        None => None,
+    };
+
+    if options.format == Format::Csv {
+        println!("{}", report::CSV_HEADER);
+    }
+
+    let start = Instant::now();
+    pool::for_each_ordered(
+        &options.days,
+        options.jobs,
+        |&day| {
+            let solver = selected_solver(day, &options);
+            solve_day(day, solver, &options, use_cache.then_some(&cache))
+        },
+        |mut report| {
+            if let Some(log_file) = &mut log_file {
+                for event in report.events.drain(..) {
+                    if let Err(err) = writeln!(log_file, "[day {:02}] {}", report.day, event) {
+                        eprintln!("Could not write the log file: {}", err);
+                    }
+                }
+            } else if options.format != Format::Text {
+                for event in &report.events {
+                    eprintln!("Day {:02}: {}", report.day, event);
+                }
+            }
+
+            for sol in report.parts.iter().flatten() {
+                if let Verdict::Fail(expected) = &sol.verdict {
+                    failures += 1;
+                    failed_days.push(format!(
+                        "Day {:02}: part {} is {}, expected {}",
+                        report.day, sol.part, sol.solution, expected
+                    ));
+                }
+            }
+            if let Some(error) = &report.error {
+                failures += 1;
+                failed_days.push(format!("Day {:02}: {}", report.day, error));
+                if options.format != Format::Text {
+                    eprintln!("Day {:02}: {}", report.day, error);
+                }
+            }
+            // The times of cached days are those of an earlier run
+            if report.cached {
+                cached_days += 1;
+            } else {
+                runtime += report.elapsed_ms();
+            }
+            used_params.extend(report.params.iter().map(|(name, _)| name.clone()));
+            // Cached answers were already recorded by the run that computed them
+            if !report.cached {
+                history_entries.extend(history::entries(&report, timestamp, &commit));
+            }
+            if let Some(key) = report.cache_key
+                && !report.cached
+                && report.error.is_none()
+                && !report
+                    .parts
+                    .iter()
+                    .flatten()
+                    .any(|sol| matches!(sol.verdict, Verdict::Fail(_)))
+            {
+                let variant = report.variant.unwrap_or_else(|| {
+                    days::get_day_solver(report.day).map_or("default", |solver| solver.variant())
+                });
+                cached_runs.push(CachedRun::from_report(&report, variant, key));
+            }
 
-    for day in days {
-        let func = days::get_day_solver(day);
-        let (input, is_example) = get_day_input(day);
+            match options.format {
+                Format::Text => report::print_text(&report),
+                Format::Csv => report::csv_rows(&report)
+                    .iter()
+                    .for_each(|row| println!("{}", row)),
+                Format::Json => reports.push(report),
+            }
+        },
+    );
+    let wall_clock_ms = start.elapsed().as_nanos() as f64 / 1_000_000.0;
+    if let Some(mut log_file) = log_file
+        && let Err(err) = log_file.flush()
+    {
+        eprintln!("Could not write the log file: {}", err);
+    }
 
-        let mut context = Context::new(input, is_example);
-        func(&mut context);
+    match options.format {
+        Format::Text => {
+            match cached_days {
+                0 => println!("Total runtime: {:.4} ms", runtime),
+                _ => println!(
+                    "Total runtime: {:.4} ms ({} cached day(s) not included)",
+                    runtime, cached_days
+                ),
+            }
+            println!(
+                "Wall-clock time: {:.4} ms ({} job(s))",
+                wall_clock_ms, options.jobs
+            );
+        }
+        Format::Json => println!("{}", report::json(&reports)),
+        Format::Csv => {}
+    }
 
-        let mut elapsed_ms = 0.0;
-        println!("=== Day {:02} ===", day);
-        match context.sol1() {
-            Some((sol, time)) => {
-                println!("  · Part 1: {}", sol);
-                println!("  · Elapsed: {:.4} ms", time);
-                elapsed_ms += time;
+    let history_path = Path::new(history::HISTORY_PATH);
+    let mut regressions = 0;
+    if let Some(threshold) = options.compare_last {
+        match history::load(history_path) {
+            Ok(history) => {
+                regressions = compare_last(&history, &history_entries, threshold, &options)
             }
-            None => println!("Part 1: Not implemented"),
+            Err(err) => eprintln!("{}", err),
+        }
+    }
+    if let Err(err) = history::append(history_path, &history_entries) {
+        eprintln!("{}", err);
+    }
+    if !cached_runs.is_empty() {
+        let mut cache = cache;
+        cached_runs.into_iter().for_each(|run| cache.insert(run));
+        if let Err(err) = cache.save(cache_path) {
+            eprintln!("{}", err);
+        }
+    }
+
+    for name in options.params.keys() {
+        if !used_params.contains(name) {
+            eprintln!("Warning: parameter {} is not used by any of the days", name);
+        }
+    }
+
+    if failures > 0 {
+        eprintln!(
+            "{} answer(s) did not match the expected value or failed to solve",
+            failures
+        );
+        for failure in &failed_days {
+            eprintln!("  · {}", failure);
+        }
+        process::exit(1);
+    }
+    if regressions > 0 {
+        eprintln!("{} part(s) regressed since the last run", regressions);
+        process::exit(1);
+    }
+}
+
+// Prints the parts whose answer changed or that got slower than in their previous
+// runs, and returns how many there are
+fn compare_last(
+    history: &[history::Entry],
+    entries: &[history::Entry],
+    threshold: f64,
+    options: &Options,
+) -> usize {
+    let mut lines = Vec::new();
+    for entry in entries {
+        let part = format!("Day {:02} part {}", entry.day, entry.part);
+        match history::regression(history, entry, threshold) {
+            Some(Regression::Answer { previous }) => lines.push(format!(
+                "{}: answer changed to {}, was {}",
+                part, entry.answer, previous
+            )),
+            Some(Regression::Time { previous_ms }) => lines.push(format!(
+                "{}: {:.4} ms, previous median {:.4} ms ({:+.1}%)",
+                part,
+                entry.elapsed_ms,
+                previous_ms,
+                (entry.elapsed_ms / previous_ms - 1.0) * 100.0
+            )),
+            None => {}
+        }
+    }
+
+    let mut output = vec!["Compared with the previous runs:".to_string()];
+    output.extend(lines.iter().map(|line| format!("  · {}", line)));
+    match history::totals(history, entries) {
+        Some((total, previous)) => output.push(format!(
+            "  Total: {:.4} ms, previous medians {:.4} ms ({:+.1}%)",
+            total,
+            previous,
+            (total / previous - 1.0) * 100.0
+        )),
+        None => output.push("  No previous run of these parts".to_string()),
+    }
+    for line in output {
+        // Keep the machine-readable formats parsable
+        match options.format {
+            Format::Text => println!("{}", line),
+            _ => eprintln!("{}", line),
         }
-        match context.sol2() {
-            Some((sol, time)) => {
-                println!("  · Part 2: {}", sol);
-                println!("  · Elapsed: {:.4} ms", time);
-                elapsed_ms += time;
+    }
+    lines.len()
+}
+
+fn new_day(args: &[String]) {
+    let (day, title) = match cli::parse_new_day(args) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, cli::USAGE);
+            process::exit(2);
+        }
+    };
+    match scaffold::new_day(day, &title) {
+        Ok(paths) => {
+            for path in paths {
+                println!("Created {}", path.display());
             }
-            None => println!("Part 2: Not implemented"),
         }
-        println!("Total: {:.4} ms\n", elapsed_ms);
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
+}
 
-        runtime += elapsed_ms;
+fn fetch(args: &[String]) {
+    let days = match cli::parse_fetch(args) {
+        Ok(days) => days,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, cli::USAGE);
+            process::exit(2);
+        }
+    };
+    let failures = fetch::fetch_days(&days);
+    if failures > 0 {
+        eprintln!("{} input(s) could not be downloaded", failures);
+        process::exit(1);
     }
+}
 
-    println!("Total runtime: {:.4} ms", runtime);
+fn list_days() {
+    for day in days::available_days() {
+        let solver = days::get_day_solver(day).unwrap();
+        let parts = match solver.parts() {
+            1 => "part 1",
+            _ => "parts 1-2",
+        };
+        let variants: Vec<&str> = days::get_variants(day)
+            .iter()
+            .map(|solver| solver.variant())
+            .collect();
+        match variants.as_slice() {
+            [_] => println!("Day {:02}  {:<24} {}", day, solver.title(), parts),
+            _ => println!(
+                "Day {:02}  {:<24} {:<10} variants: {}",
+                day,
+                solver.title(),
+                parts,
+                variants.join(", ")
+            ),
+        }
+    }
 }
 
-fn get_day_input(day: u8) -> (Vec<String>, bool) {
-    // If environment variable EXAMPLE is set, use example input
-    if env::var("EXAMPLE").is_ok() {
-        return (example::EXAMPLE
-            .lines()
-            .map(|l| l.to_string())
-            .collect::<Vec<String>>(), true);
-    }
-
-    let path = format!("inputs/day{:02}.txt", day);
-    match aoc::read_lines_as_vec(&path) {
-        Ok(lines) => (lines, false),
-        Err(_) => {
-            let path = format!("inputs/day{}.txt", day);
-            match aoc::read_lines_as_vec(&path) {
-                Ok(lines) => (lines, false),
-                Err(_) => panic!("Could not find input file"),
+fn selected_solver(day: u8, options: &Options) -> &'static dyn DaySolver {
+    // Days and variants were validated while parsing the arguments
+    match &options.variant {
+        Some(variant) => days::get_variant(day, variant),
+        None => days::get_day_solver(day),
+    }
+    .unwrap()
+}
+
+// Reuses the answers of `cache` when the day already ran with the same code and input
+fn solve_day(
+    day: u8,
+    solver: &'static dyn DaySolver,
+    options: &Options,
+    cache: Option<&Cache>,
+) -> DayReport {
+    // Only worth showing when it was picked
+    let variant = (options.variant.is_some() || options.compare).then(|| solver.variant());
+    let input = match DayInput::load(day, options) {
+        Ok(input) => input,
+        Err(error) => {
+            return DayReport {
+                day,
+                variant,
+                is_example: options.example,
+                bench: options.bench,
+                part: options.part,
+                parse: None,
+                parts: [None, None],
+                params: Vec::new(),
+                profile: Vec::new(),
+                events: Vec::new(),
+                error: Some(error),
+                cached: false,
+                cache_key: None,
+            };
+        }
+    };
+
+    let cache_key = cache.and_then(|_| cache::key(day, solver.variant(), &input, options));
+    let cached = cache.zip(cache_key).and_then(|(cache, key)| cache.get(key));
+    let run = match cached {
+        Some(cached) => cached.to_run(),
+        None => run_day(solver, &input, options, true),
+    };
+
+    // Samples for parsing and each part. No point in benchmarking a day that fails.
+    let mut samples = [Vec::new(), Vec::new(), Vec::new()];
+    if let Some(bench) = &options.bench
+        && run.error.is_none()
+    {
+        for _ in 0..bench.warmup {
+            run_day(solver, &input, options, false);
+        }
+        for _ in 0..bench.runs {
+            let run = run_day(solver, &input, options, false);
+            let part_times = run
+                .sols
+                .iter()
+                .map(|sol| sol.as_ref().map(|(_, time)| *time));
+            for (samples, time) in samples
+                .iter_mut()
+                .zip([run.parse_ms].into_iter().chain(part_times))
+            {
+                samples.extend(time);
             }
         }
     }
+    // Phase 0 is parsing, then each part
+    let timing = |phase: usize, time_ms| Timing {
+        time_ms,
+        stats: (!samples[phase].is_empty()).then(|| Stats::from_samples(&samples[phase])),
+        allocs: run.allocs[phase],
+    };
+
+    let params: Vec<(String, String)> = run
+        .used_params
+        .iter()
+        .filter_map(|name| Some((name.clone(), options.params.get(name)?.clone())))
+        .collect();
+    // The stored answers are those of the puzzle's own parameters, so they
+    // aren't checked once the day used an override
+    let answers = if params.is_empty() {
+        Answers::load(input.answers.as_deref()).unwrap_or_else(|err| {
+            eprintln!("{}", err);
+            Answers::default()
+        })
+    } else {
+        Answers::default()
+    };
+
+    let mut parts = [1, 2].into_iter().zip(run.sols).map(|(part, sol)| {
+        sol.map(|(solution, time_ms)| PartReport {
+            part,
+            verdict: answers.check(part, &solution),
+            solution,
+            timing: timing(part as usize, time_ms),
+        })
+    });
+
+    DayReport {
+        day,
+        variant,
+        is_example: input.is_example,
+        bench: options.bench,
+        part: options.part,
+        parse: run.parse_ms.map(|time_ms| timing(0, time_ms)),
+        parts: [parts.next().unwrap(), parts.next().unwrap()],
+        params,
+        // Spans of the first run, only recorded with --profile
+        profile: run.profile,
+        events: run.events,
+        error: run.error,
+        cached: cached.is_some(),
+        cache_key,
+    }
+}
+
+pub struct DayInput {
+    pub part1: Vec<String>,
+    // Only set when part 2 needs a different input, which happens for some examples
+    pub part2: Option<Vec<String>>,
+    pub is_example: bool,
+    answers: Option<PathBuf>,
+}
+
+impl DayInput {
+    fn load(day: u8, options: &Options) -> aoc::Result<Self> {
+        let read = |source: &InputSource| {
+            source
+                .read(day)
+                .map_err(|err| aoc::Error::input(err.to_string()))
+        };
+
+        // The example registry is only used when no input was given explicitly
+        if options.example && options.input.is_none() {
+            let example = |part| {
+                example::example_path(day, part)
+                    .map(InputSource::File)
+                    .ok_or_else(|| {
+                        aoc::Error::input(format!(
+                            "no example for day {} in {}",
+                            day,
+                            example::EXAMPLES_DIR
+                        ))
+                    })
+            };
+            let part1 = read(&example(1)?)?;
+            let part2 = read(&example(2)?)?;
+            return Ok(DayInput {
+                part2: (part2 != part1).then_some(part2),
+                part1,
+                is_example: true,
+                answers: Some(example::answers_path(day)),
+            });
+        }
+
+        let default_source = InputSource::default_dir();
+        let source = options.input.as_ref().unwrap_or(&default_source);
+        Ok(DayInput {
+            part1: read(source)?,
+            part2: None,
+            is_example: options.example,
+            answers: source.answers_path(day),
+        })
+    }
+}
+
+pub struct DayRun {
+    pub parse_ms: Option<f64>,
+    pub sols: [Option<(Solution, f64)>; 2],
+    // Parsing, then each part
+    pub allocs: [Option<AllocStats>; 3],
+    pub used_params: Vec<String>,
+    pub profile: Vec<SpanRecord>,
+    pub events: Vec<Event>,
+    pub error: Option<aoc::Error>,
+}
+
+impl DayRun {
+    fn from_context(context: &Context, result: aoc::Result<()>) -> Self {
+        let sol = |sol: Option<(&Solution, f64)>| sol.map(|(sol, time)| (sol.clone(), time));
+        DayRun {
+            parse_ms: context.parse_time(),
+            sols: [sol(context.sol1()), sol(context.sol2())],
+            allocs: [
+                context.parse_allocs(),
+                context.sol1_allocs(),
+                context.sol2_allocs(),
+            ],
+            used_params: context
+                .used_params()
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            profile: context.profile(),
+            events: context.events(),
+            error: result.err(),
+        }
+    }
+}
+
+// Runs the selected part(s) of the solver on a fresh context and returns the solution
+// and time of each part, along with the error that stopped the solver, if any.
+// Diagnostics are only collected when `traced`, to keep them out of benchmarks.
+fn run_day(
+    solver: &'static dyn DaySolver,
+    input: &DayInput,
+    options: &Options,
+    traced: bool,
+) -> DayRun {
+    let deadline = options.timeout.map(|timeout| Instant::now() + timeout);
+    let run = |lines: &[String], part| {
+        let mut context = Context::new(lines.to_vec(), input.is_example);
+        context.set_part(part);
+        context.set_params(options.params.clone());
+        context.set_log_level(options.log_level.filter(|_| traced));
+        context.set_profiling(options.profile && traced);
+        run_with_deadline(solver, context, deadline)
+    };
+
+    let part = options.part;
+    let Some(part2) = input.part2.as_ref().filter(|_| part != Some(1)) else {
+        return run(&input.part1, part);
+    };
+
+    // With a separate input for part 2, each part gets a run of its own
+    let first = part.is_none().then(|| run(&input.part1, Some(1)));
+    let second = run(part2, Some(2));
+    match first {
+        Some(first) => DayRun {
+            parse_ms: first.parse_ms,
+            sols: [first.sols[0].clone(), second.sols[1].clone()],
+            allocs: [first.allocs[0], first.allocs[1], second.allocs[2]],
+            used_params: [first.used_params, second.used_params].concat(),
+            profile: [first.profile, second.profile].concat(),
+            events: [first.events, second.events].concat(),
+            error: first.error.or(second.error),
+        },
+        None => second,
+    }
+}
+
+fn run_with_deadline(
+    solver: &'static dyn DaySolver,
+    mut context: Context,
+    deadline: Option<Instant>,
+) -> DayRun {
+    let Some(deadline) = deadline else {
+        let result = panics::catch(|| solver.run(&mut context)).and_then(|result| result);
+        return DayRun::from_context(&context, result);
+    };
+
+    // The solver gets a thread of its own, so that a day that never checks
+    // `should_stop` can still be given up on. The phases it finished are kept
+    // in `progress`, so that a timeout doesn't lose them.
+    let stop = context.stop_flag();
+    let progress = Arc::new(Mutex::new(None));
+    let latest = Arc::clone(&progress);
+    context.on_progress(move |context| {
+        *latest.lock().unwrap() = Some(DayRun::from_context(context, Ok(())));
+    });
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let result = panics::catch(|| solver.run(&mut context)).and_then(|result| result);
+        let _ = sender.send(DayRun::from_context(&context, result));
+    });
+    let timeout = deadline.saturating_duration_since(Instant::now());
+    receiver.recv_timeout(timeout).unwrap_or_else(|_| {
+        stop.store(true, Ordering::Relaxed);
+        let progress = progress.lock().unwrap().take();
+        DayRun {
+            error: Some(aoc::Error::Timeout),
+            ..progress.unwrap_or(DayRun {
+                parse_ms: None,
+                sols: [None, None],
+                allocs: [None; 3],
+                used_params: Vec::new(),
+                profile: Vec::new(),
+                events: Vec::new(),
+                error: None,
+            })
+        }
+    })
 }
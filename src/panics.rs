@@ -0,0 +1,45 @@
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+
+thread_local! {
+    // Set while `catch` runs on this thread
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static LAST_PANIC: RefCell<Option<aoc::Error>> = const { RefCell::new(None) };
+}
+
+// Records the message and location of panics caught by `catch` instead of printing
+// them. Other panics are still reported by the default hook.
+pub fn install_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if !CATCHING.get() {
+            default_hook(info);
+            return;
+        }
+        let message = info.payload_as_str().unwrap_or("Box<dyn Any>");
+        // Day modules are included from build.rs with absolute paths
+        let location = info.location().map_or_else(
+            || "unknown location".to_string(),
+            |l| {
+                let file = l.file();
+                let file = file
+                    .strip_prefix(concat!(env!("CARGO_MANIFEST_DIR"), "/"))
+                    .unwrap_or(file);
+                format!("{}:{}:{}", file, l.line(), l.column())
+            },
+        );
+        LAST_PANIC.set(Some(aoc::Error::panic(message, location)));
+    }));
+}
+
+// Runs `f`, turning a panic into an error so that the other days can still run
+pub fn catch<T, F: FnOnce() -> T>(f: F) -> aoc::Result<T> {
+    CATCHING.set(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(false);
+    result.map_err(|_| {
+        LAST_PANIC
+            .take()
+            .unwrap_or_else(|| aoc::Error::panic("unknown panic", "unknown location"))
+    })
+}
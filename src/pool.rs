@@ -0,0 +1,52 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+// Runs `work` on every item using up to `jobs` threads. Results are handed to
+// `on_result` in the same order as `items`, as soon as all earlier ones are done.
+pub fn for_each_ordered<T, R, W, F>(items: &[T], jobs: usize, work: W, mut on_result: F)
+where
+    T: Sync,
+    R: Send,
+    W: Fn(&T) -> R + Sync,
+    F: FnMut(R),
+{
+    if jobs <= 1 || items.len() <= 1 {
+        items.iter().map(&work).for_each(on_result);
+        return;
+    }
+
+    let next_item = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            let sender = sender.clone();
+            let next_item = &next_item;
+            let work = &work;
+            scope.spawn(move || {
+                loop {
+                    let index = next_item.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(index) else {
+                        break;
+                    };
+                    if sender.send((index, work(item))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next_result = 0;
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&next_result) {
+                on_result(result);
+                next_result += 1;
+            }
+        }
+    });
+}
@@ -0,0 +1,226 @@
+use aoc::days;
+use aoc::{AllocStats, Event, Solution, SpanRecord};
+
+use std::fmt::Write;
+
+use crate::answers::Verdict;
+use crate::bench::Stats;
+use crate::cli::Bench;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+pub struct Timing {
+    pub time_ms: f64,
+    pub stats: Option<Stats>,
+    // Of the first run, with the `alloc-stats` feature
+    pub allocs: Option<AllocStats>,
+}
+
+pub struct PartReport {
+    pub part: u8,
+    pub solution: Solution,
+    pub timing: Timing,
+    pub verdict: Verdict,
+}
+
+pub struct DayReport {
+    pub day: u8,
+    pub variant: Option<&'static str>,
+    pub is_example: bool,
+    pub bench: Option<Bench>,
+    // The only part that was run, if one was selected
+    pub part: Option<u8>,
+    pub parse: Option<Timing>,
+    pub parts: [Option<PartReport>; 2],
+    // Parameter overrides the day used
+    pub params: Vec<(String, String)>,
+    // Only filled with --profile
+    pub profile: Vec<SpanRecord>,
+    // Diagnostics of the solver, with -v or -vv
+    pub events: Vec<Event>,
+    pub error: Option<aoc::Error>,
+    // Whether the answers come from the result cache instead of a run
+    pub cached: bool,
+    pub cache_key: Option<u64>,
+}
+
+impl Timing {
+    // In bench mode the median is more representative than the last run
+    pub fn elapsed_ms(&self) -> f64 {
+        self.stats
+            .as_ref()
+            .map_or(self.time_ms, |stats| stats.median)
+    }
+}
+
+impl DayReport {
+    pub fn elapsed_ms(&self) -> f64 {
+        let parts: f64 = self
+            .parts
+            .iter()
+            .flatten()
+            .map(|sol| sol.timing.elapsed_ms())
+            .sum();
+        parts + self.parse_ms()
+    }
+
+    pub fn parse_ms(&self) -> f64 {
+        self.parse.as_ref().map_or(0.0, Timing::elapsed_ms)
+    }
+}
+
+pub fn print_text(report: &DayReport) {
+    print_header(report.day, report.variant);
+    for (name, value) in &report.params {
+        println!("  · Param: {} = {}", name, value);
+    }
+    if let Some(parse) = &report.parse {
+        print!("  · Parse: ");
+        print_timing(parse, report.bench.as_ref());
+    }
+    for (part, sol) in [1, 2].into_iter().zip(&report.parts) {
+        match sol {
+            Some(sol) => {
+                let cached = if report.cached { " (cached)" } else { "" };
+                println!(
+                    "  · Part {}: {} [{}]{}",
+                    part, sol.solution, sol.verdict, cached
+                );
+                print!("  · Elapsed: ");
+                print_timing(&sol.timing, report.bench.as_ref());
+            }
+            None if report.part.is_some_and(|selected| selected != part) => {
+                println!("Part {}: Skipped", part)
+            }
+            None if report.error == Some(aoc::Error::Timeout) => {
+                println!("Part {}: TIMEOUT", part)
+            }
+            None if report.error.is_some() => println!("Part {}: Not solved", part),
+            None => println!("Part {}: Not implemented", part),
+        }
+    }
+    if !report.profile.is_empty() {
+        println!("  · Profile:");
+        for span in &report.profile {
+            let name = format!("{}{}", "  ".repeat(span.depth), span.name);
+            print!("      {:<30} {:.4} ms", name, span.total_ms);
+            if span.calls > 1 {
+                print!(" ({} calls)", span.calls);
+            }
+            println!();
+        }
+    }
+    for event in &report.events {
+        println!("  · {}", event);
+    }
+    if let Some(error) = &report.error {
+        println!("  · Error: {}", error);
+    }
+    let cached = if report.cached { " (cached)" } else { "" };
+    println!("Total: {:.4} ms{}\n", report.elapsed_ms(), cached);
+}
+
+pub fn print_header(day: u8, variant: Option<&str>) {
+    let title =
+        days::get_day_solver(day).map_or(String::new(), |solver| format!(": {}", solver.title()));
+    match variant {
+        Some(variant) => println!("=== Day {:02}{} ({}) ===", day, title, variant),
+        None => println!("=== Day {:02}{} ===", day, title),
+    }
+}
+
+fn print_timing(timing: &Timing, bench: Option<&Bench>) {
+    match (&timing.stats, bench) {
+        (Some(stats), Some(bench)) => {
+            print!("{} ({} runs, {} warm-up)", stats, bench.runs, bench.warmup)
+        }
+        _ => print!("{:.4} ms", timing.time_ms),
+    }
+    match &timing.allocs {
+        Some(allocs) => println!(" · {}", allocs),
+        None => println!(),
+    }
+}
+
+pub const CSV_HEADER: &str = "day,part,answer,type,elapsed_ms,parse_ms,example,cached";
+
+pub fn csv_rows(report: &DayReport) -> Vec<String> {
+    report
+        .parts
+        .iter()
+        .flatten()
+        .map(|sol| {
+            format!(
+                "{},{},{},{},{:.6},{:.6},{},{}",
+                report.day,
+                sol.part,
+                csv_field(&sol.solution.to_string()),
+                sol.solution.kind(),
+                sol.timing.elapsed_ms(),
+                report.parse_ms(),
+                report.is_example,
+                report.cached
+            )
+        })
+        .collect()
+}
+
+pub fn json(reports: &[DayReport]) -> String {
+    let records: Vec<String> = reports
+        .iter()
+        .flat_map(|report| {
+            report.parts.iter().flatten().map(|sol| {
+                format!(
+                    "  {{\"day\": {}, \"part\": {}, \"answer\": {}, \"type\": \"{}\", \"elapsed_ms\": {:.6}, \"parse_ms\": {:.6}, \"example\": {}, \"cached\": {}}}",
+                    report.day,
+                    sol.part,
+                    json_string(&sol.solution.to_string()),
+                    sol.solution.kind(),
+                    sol.timing.elapsed_ms(),
+                    report.parse_ms(),
+                    report.is_example,
+                    report.cached
+                )
+            })
+        })
+        .collect();
+
+    if records.is_empty() {
+        "[]".to_string()
+    } else {
+        format!("[\n{}\n]", records.join(",\n"))
+    }
+}
+
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
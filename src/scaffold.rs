@@ -0,0 +1,76 @@
+use aoc::{InputSource, example};
+
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+// Source of a new day. build.rs registers every `src/days/dayNN.rs`, so the file
+// is all it takes to add the day to the runner.
+const TEMPLATE: &str = r#"use crate::{Context, Error, Result, Solution, Solver};
+
+pub struct Day{day};
+
+impl Solver for Day{day} {
+    type Parsed = Vec<String>;
+
+    const TITLE: &'static str = "{title}";
+
+    fn parse(&self, input: &[String]) -> Result<Self::Parsed> {
+        Ok(input.to_vec())
+    }
+
+    fn part1(&self, _lines: &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        Err(Error::no_solution("part 1 is not implemented"))
+    }
+
+    fn part2(&self, _lines: &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        Err(Error::no_solution("part 2 is not implemented"))
+    }
+}
+"#;
+
+// Creates the solver, input and example files of `day`, and returns their paths.
+// Nothing is written when any of them already exists.
+pub fn new_day(day: u8, title: &str) -> Result<Vec<PathBuf>, String> {
+    let source = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("days")
+        .join(format!("day{:02}.rs", day));
+    // The first one is `dayNN.txt`, `dayN.txt` is only there to check it doesn't exist
+    let inputs = InputSource::default_dir().candidate_paths(day);
+    let example = Path::new(example::EXAMPLES_DIR).join(format!("day{:02}.txt", day));
+    let code = TEMPLATE
+        .replace("{day}", &format!("{:02}", day))
+        .replace("{title}", &title.replace('\\', "\\\\").replace('"', "\\\""));
+    let files = [
+        (source, code),
+        (inputs[0].clone(), String::new()),
+        (example, String::new()),
+    ];
+
+    let mut existing = files.iter().map(|(path, _)| path).chain(&inputs);
+    if let Some(path) = existing.find(|path| path.exists()) {
+        return Err(format!("{} already exists", path.display()));
+    }
+    for (path, content) in &files {
+        write_new(path, content)?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+// Fails instead of overwriting the file if it appeared in the meantime
+fn write_new(path: &Path, content: &str) -> Result<(), String> {
+    let error = |err: std::io::Error| match err.kind() {
+        ErrorKind::AlreadyExists => format!("{} already exists", path.display()),
+        _ => format!("Could not write {}: {}", path.display(), err),
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(error)?;
+    }
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .map_err(error)
+}
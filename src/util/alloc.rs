@@ -0,0 +1,150 @@
+use std::fmt::{self, Display};
+
+// Allocations made by one phase of a solver
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AllocStats {
+    pub count: u64,
+    pub bytes: u64,
+    // Highest live size reached during the phase, above what was live when it started
+    pub peak_bytes: u64,
+}
+
+// Counters of the current thread at some point in time
+#[cfg(feature = "alloc-stats")]
+#[derive(Clone, Copy)]
+pub struct AllocMark {
+    count: u64,
+    bytes: u64,
+    live: i64,
+}
+
+#[cfg(not(feature = "alloc-stats"))]
+#[derive(Clone, Copy)]
+pub struct AllocMark;
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    // Counters are kept per thread so that days running concurrently don't mix
+    // up their numbers. Memory freed by another thread than the one that
+    // allocated it can make `LIVE` negative.
+    thread_local! {
+        pub static COUNT: Cell<u64> = const { Cell::new(0) };
+        pub static BYTES: Cell<u64> = const { Cell::new(0) };
+        pub static LIVE: Cell<i64> = const { Cell::new(0) };
+        pub static PEAK: Cell<i64> = const { Cell::new(0) };
+    }
+
+    pub struct CountingAllocator;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn record_alloc(size: usize) {
+        let _ = COUNT.try_with(|count| count.set(count.get() + 1));
+        let _ = BYTES.try_with(|bytes| bytes.set(bytes.get() + size as u64));
+        record_live(size as i64);
+    }
+
+    fn record_live(delta: i64) {
+        let _ = LIVE.try_with(|live| {
+            live.set(live.get() + delta);
+            let _ = PEAK.try_with(|peak| peak.set(peak.get().max(live.get())));
+        });
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            record_alloc(layout.size());
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            record_alloc(layout.size());
+            unsafe { System.alloc_zeroed(layout) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            record_live(-(layout.size() as i64));
+            unsafe { System.dealloc(ptr, layout) }
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            // Counted as a new allocation of the whole new size
+            let _ = COUNT.try_with(|count| count.set(count.get() + 1));
+            let _ = BYTES.try_with(|bytes| bytes.set(bytes.get() + new_size as u64));
+            record_live(new_size as i64 - layout.size() as i64);
+            unsafe { System.realloc(ptr, layout, new_size) }
+        }
+    }
+}
+
+impl AllocMark {
+    // Current counters of this thread, or `None` without the `alloc-stats` feature.
+    // Starts a new peak measurement.
+    #[cfg(feature = "alloc-stats")]
+    pub fn now() -> Option<Self> {
+        use counting::*;
+        let live = LIVE.get();
+        PEAK.set(live);
+        Some(AllocMark {
+            count: COUNT.get(),
+            bytes: BYTES.get(),
+            live,
+        })
+    }
+
+    #[cfg(not(feature = "alloc-stats"))]
+    pub fn now() -> Option<Self> {
+        None
+    }
+
+    // Allocations since `self`, which has to come from the current thread
+    #[cfg(feature = "alloc-stats")]
+    pub fn elapsed(&self) -> AllocStats {
+        use counting::*;
+        AllocStats {
+            count: COUNT.get() - self.count,
+            bytes: BYTES.get() - self.bytes,
+            peak_bytes: (PEAK.get() - self.live).max(0) as u64,
+        }
+    }
+
+    #[cfg(not(feature = "alloc-stats"))]
+    pub fn elapsed(&self) -> AllocStats {
+        AllocStats::default()
+    }
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} allocated, peak {}",
+            self.count,
+            ByteSize(self.bytes),
+            ByteSize(self.peak_bytes)
+        )
+    }
+}
+
+struct ByteSize(u64);
+
+impl Display for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+        let mut size = self.0 as f64;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < UNITS.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+        if unit == 0 {
+            write!(f, "{} B", self.0)
+        } else {
+            write!(f, "{:.1} {}", size, UNITS[unit])
+        }
+    }
+}
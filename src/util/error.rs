@@ -0,0 +1,150 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    // Line and column are 1-based, as shown to the user
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    MissingSection(String),
+    NoSolution(String),
+    Input(String),
+    // A `--param` override that doesn't fit the parameter
+    Param {
+        name: String,
+        value: String,
+        message: String,
+    },
+    // The solver was stopped, see `Context::should_stop`
+    Timeout,
+    // The solver panicked; only produced by the runner
+    Panic {
+        message: String,
+        location: String,
+    },
+}
+
+impl Error {
+    pub fn parse<M: Into<String>>(line: usize, column: usize, message: M) -> Self {
+        Error::Parse {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    // Error pointing at `field`, which has to be a slice of `line`. `index` is the
+    // 0-based position of `line` in the input.
+    pub fn at<M: Into<String>>(index: usize, line: &str, field: &str, message: M) -> Self {
+        Error::parse(index + 1, column_of(line, field), message)
+    }
+
+    pub fn missing_section<M: Into<String>>(name: M) -> Self {
+        Error::MissingSection(name.into())
+    }
+
+    pub fn no_solution<M: Into<String>>(message: M) -> Self {
+        Error::NoSolution(message.into())
+    }
+
+    pub fn input<M: Into<String>>(message: M) -> Self {
+        Error::Input(message.into())
+    }
+
+    pub fn param<N: Into<String>, V: Into<String>, M: Into<String>>(
+        name: N,
+        value: V,
+        message: M,
+    ) -> Self {
+        Error::Param {
+            name: name.into(),
+            value: value.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn panic<M: Into<String>, L: Into<String>>(message: M, location: L) -> Self {
+        Error::Panic {
+            message: message.into(),
+            location: location.into(),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse {
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "parse error at line {}, column {}: {}",
+                line, column, message
+            ),
+            Error::MissingSection(name) => write!(f, "missing section: {}", name),
+            Error::NoSolution(message) => write!(f, "no solution: {}", message),
+            Error::Input(message) => write!(f, "could not read input: {}", message),
+            Error::Param {
+                name,
+                value,
+                message,
+            } => write!(f, "invalid parameter {}={}: {}", name, value, message),
+            Error::Timeout => write!(f, "timed out"),
+            Error::Panic { message, location } => {
+                write!(f, "panicked at {}: {}", location, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+// 1-based column of `field` inside `line`, or the column just past the end of
+// the line if `field` doesn't point into it
+fn column_of(line: &str, field: &str) -> usize {
+    let offset = (field.as_ptr() as usize)
+        .checked_sub(line.as_ptr() as usize)
+        .filter(|&offset| offset <= line.len() && line.is_char_boundary(offset))
+        .unwrap_or(line.len());
+    line[..offset].chars().count() + 1
+}
+
+// Parses `field`, a slice of `line`, reporting failures at its position
+pub fn parse_field<T>(index: usize, line: &str, field: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    field.parse().map_err(|err| {
+        Error::at(
+            index,
+            line,
+            field,
+            format!("invalid value {:?}: {}", field, err),
+        )
+    })
+}
+
+// Unwraps a field produced by splitting `line`, reporting a missing field at the end of the line
+pub fn require_field<'a>(
+    index: usize,
+    line: &'a str,
+    field: Option<&'a str>,
+    what: &str,
+) -> Result<&'a str> {
+    field.ok_or_else(|| {
+        Error::at(
+            index,
+            line,
+            &line[line.len()..],
+            format!("missing {}", what),
+        )
+    })
+}
@@ -0,0 +1,25 @@
+use std::path::{Path, PathBuf};
+
+// Example inputs live next to the real inputs, one file per day. A day whose
+// parts use different examples can add `dayNN.partP.txt`, which takes
+// precedence over the shared `dayNN.txt` for that part.
+pub const EXAMPLES_DIR: &str = "inputs/examples";
+
+pub fn example_path(day: u8, part: u8) -> Option<PathBuf> {
+    candidate_paths(day, part)
+        .into_iter()
+        .find(|path| path.is_file())
+}
+
+// Files the example of `part` may come from, by order of precedence
+pub fn candidate_paths(day: u8, part: u8) -> [PathBuf; 2] {
+    let dir = Path::new(EXAMPLES_DIR);
+    [
+        dir.join(format!("day{:02}.part{}.txt", day, part)),
+        dir.join(format!("day{:02}.txt", day)),
+    ]
+}
+
+pub fn answers_path(day: u8) -> PathBuf {
+    Path::new(EXAMPLES_DIR).join(format!("day{:02}.answers", day))
+}
@@ -1,3 +1,4 @@
+use crate::util::error::*;
 use crate::util::point::*;
 
 use std::borrow::Borrow;
@@ -26,6 +27,26 @@ impl Grid<u8> {
         }
     }
 
+    // Like `parse`, but rejects empty input and rows of different widths
+    pub fn try_parse(input: &[String]) -> Result<Self> {
+        let width = match input.first() {
+            Some(first) => first.len(),
+            None => return Err(Error::missing_section("grid")),
+        };
+        for (index, line) in input.iter().enumerate() {
+            let row = line.trim();
+            if row.len() != width {
+                return Err(Error::at(
+                    index,
+                    line,
+                    &row[row.len()..],
+                    format!("expected {} columns, found {}", width, row.len()),
+                ));
+            }
+        }
+        Ok(Grid::parse(input))
+    }
+
     pub fn parse_str(input: &str) -> Self {
         Grid::parse(
             &input
@@ -0,0 +1,114 @@
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, ErrorKind};
+use std::path::{Path, PathBuf};
+
+// Overrides the directory the day inputs are read from
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+pub const DEFAULT_INPUT_DIR: &str = "inputs";
+
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+    Inline(String),
+    // Directory holding one `dayNN.txt` (or `dayN.txt`) file per day
+    Dir(PathBuf),
+}
+
+impl InputSource {
+    // Input directory from the environment, falling back to `inputs/`
+    pub fn default_dir() -> Self {
+        let dir = env::var_os(INPUT_DIR_VAR).unwrap_or_else(|| DEFAULT_INPUT_DIR.into());
+        InputSource::Dir(dir.into())
+    }
+
+    // `-` means stdin, anything else is a file or a directory of inputs
+    pub fn from_arg(arg: &str) -> Self {
+        let path = PathBuf::from(arg);
+        if arg == "-" {
+            InputSource::Stdin
+        } else if path.is_dir() {
+            InputSource::Dir(path)
+        } else {
+            InputSource::File(path)
+        }
+    }
+
+    // Whether the source can provide input for several different days
+    pub fn is_per_day(&self) -> bool {
+        matches!(self, InputSource::Dir(_))
+    }
+
+    pub fn read(&self, day: u8) -> io::Result<Vec<String>> {
+        match self {
+            InputSource::File(path) => read_lines_as_vec(path),
+            InputSource::Stdin => read_all_lines(io::stdin().lock()),
+            InputSource::Inline(input) => Ok(input.lines().map(str::to_owned).collect()),
+            InputSource::Dir(dir) => {
+                let path = day_file(dir, day).ok_or_else(|| {
+                    io::Error::new(
+                        ErrorKind::NotFound,
+                        format!(
+                            "no input for day {} in {} (expected day{:02}.txt)",
+                            day,
+                            dir.display(),
+                            day
+                        ),
+                    )
+                })?;
+                read_lines_as_vec(path)
+            }
+        }
+    }
+
+    // Files `read` may take the input of `day` from, whether they exist or not
+    pub fn candidate_paths(&self, day: u8) -> Vec<PathBuf> {
+        match self {
+            InputSource::File(path) => vec![path.clone()],
+            InputSource::Dir(dir) => day_files(dir, day).to_vec(),
+            InputSource::Stdin | InputSource::Inline(_) => Vec::new(),
+        }
+    }
+
+    // Known answers live next to the input file, see `answers.rs` in the runner
+    pub fn answers_path(&self, day: u8) -> Option<PathBuf> {
+        match self {
+            InputSource::File(path) => Some(path.with_extension("answers")),
+            InputSource::Dir(dir) => Some(dir.join(format!("day{:02}.answers", day))),
+            InputSource::Stdin | InputSource::Inline(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::File(path) | InputSource::Dir(path) => path.display().fmt(f),
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::Inline(_) => write!(f, "<inline>"),
+        }
+    }
+}
+
+fn day_file(dir: &Path, day: u8) -> Option<PathBuf> {
+    day_files(dir, day).into_iter().find(|path| path.is_file())
+}
+
+fn day_files(dir: &Path, day: u8) -> [PathBuf; 2] {
+    [format!("day{:02}.txt", day), format!("day{}.txt", day)].map(|name| dir.join(name))
+}
+
+// Unlike `BufRead::lines` followed by `map_while(Result::ok)`, stops at the first
+// I/O or UTF-8 error and reports it instead of returning a truncated input
+pub fn read_lines_as_vec<P: AsRef<Path>>(path: P) -> io::Result<Vec<String>> {
+    let path = path.as_ref();
+    let file = File::open(path)
+        .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))?;
+    read_all_lines(BufReader::new(file))
+        .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))
+}
+
+fn read_all_lines<R: BufRead>(reader: R) -> io::Result<Vec<String>> {
+    reader.lines().collect()
+}
@@ -0,0 +1,107 @@
+use std::cell::RefCell;
+use std::time::Instant;
+
+// Time spent in one named span. Spans opened several times under the same
+// parent, e.g. inside a loop, are merged into a single record.
+#[derive(Clone, Debug)]
+pub struct SpanRecord {
+    pub name: &'static str,
+    pub depth: usize,
+    pub calls: usize,
+    pub total_ms: f64,
+    parent: Option<usize>,
+}
+
+// Span tree of a run, in the order the spans were first opened
+#[derive(Default)]
+pub struct Profile {
+    records: RefCell<Vec<SpanRecord>>,
+    // Indices of the spans that are currently open, innermost last
+    open: RefCell<Vec<usize>>,
+}
+
+// Records the time until it is dropped, see `Context::span`
+#[must_use = "the span ends when this guard is dropped"]
+pub struct Span<'a> {
+    // Nothing is recorded when profiling is disabled
+    open: Option<OpenSpan<'a>>,
+}
+
+struct OpenSpan<'a> {
+    profile: &'a Profile,
+    index: usize,
+    start: Instant,
+}
+
+impl Span<'_> {
+    pub fn disabled() -> Self {
+        Span { open: None }
+    }
+}
+
+impl Profile {
+    pub fn enter(&self, name: &'static str) -> Span<'_> {
+        let mut records = self.records.borrow_mut();
+        let mut open = self.open.borrow_mut();
+        let parent = open.last().copied();
+        let index = match records
+            .iter()
+            .position(|r| r.parent == parent && r.name == name)
+        {
+            Some(index) => index,
+            None => {
+                records.push(SpanRecord {
+                    name,
+                    depth: open.len(),
+                    calls: 0,
+                    total_ms: 0.0,
+                    parent,
+                });
+                records.len() - 1
+            }
+        };
+        open.push(index);
+        Span {
+            open: Some(OpenSpan {
+                profile: self,
+                index,
+                start: Instant::now(),
+            }),
+        }
+    }
+
+    // Records of the spans, each one directly followed by its children
+    pub fn records(&self) -> Vec<SpanRecord> {
+        let records = self.records.borrow();
+        let mut ordered = Vec::with_capacity(records.len());
+        let mut stack: Vec<usize> = (0..records.len())
+            .rev()
+            .filter(|&i| records[i].parent.is_none())
+            .collect();
+        while let Some(index) = stack.pop() {
+            ordered.push(records[index].clone());
+            stack.extend(
+                (0..records.len())
+                    .rev()
+                    .filter(|&i| records[i].parent == Some(index)),
+            );
+        }
+        ordered
+    }
+}
+
+impl Drop for Span<'_> {
+    fn drop(&mut self) {
+        let Some(span) = &self.open else {
+            return;
+        };
+        let elapsed = span.start.elapsed().as_nanos() as f64 / 1_000_000.0;
+        let mut records = span.profile.records.borrow_mut();
+        records[span.index].calls += 1;
+        records[span.index].total_ms += elapsed;
+        let mut open = span.profile.open.borrow_mut();
+        if let Some(position) = open.iter().rposition(|&i| i == span.index) {
+            open.remove(position);
+        }
+    }
+}
@@ -1,14 +1,49 @@
+use crate::util::alloc::*;
+use crate::util::profile::*;
+use crate::util::trace::*;
+
+use crate::util::error::Error;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter, Result};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Instant;
 
+type ProgressHook = Box<dyn Fn(&Context) + Send>;
+
 pub struct Context {
     input: Vec<String>,
     is_example: bool,
+    // Only this part is run when set
+    part: Option<u8>,
+    // Overrides of the puzzle parameters, and the parameters asked for so far
+    params: HashMap<String, String>,
+    used_params: RefCell<Vec<&'static str>>,
+    // Raised by the runner when the day runs out of time
+    stop: Arc<AtomicBool>,
+    // Spans are only recorded when profiling
+    profiling: bool,
+    profile: Profile,
+    // Events at or below this level are kept, none when unset
+    log_level: Option<Level>,
+    events: RefCell<Vec<Event>>,
     sol1: Option<Solution>,
     sol2: Option<Solution>,
-    base_time: Instant,
+    // Every phase is timed from the end of the previous one
+    last_mark: Instant,
+    last_alloc_mark: Option<AllocMark>,
+    parse_time_ms: Option<f64>,
     sol1_time_ms: f64,
     sol2_time_ms: f64,
+    // Only measured with the `alloc-stats` feature
+    parse_allocs: Option<AllocStats>,
+    sol1_allocs: Option<AllocStats>,
+    sol2_allocs: Option<AllocStats>,
+    // Called every time a phase ends
+    on_progress: Option<ProgressHook>,
 }
 
 impl Context {
@@ -16,11 +51,25 @@ impl Context {
         Self {
             input,
             is_example,
+            part: None,
+            params: HashMap::new(),
+            used_params: RefCell::new(Vec::new()),
+            stop: Arc::new(AtomicBool::new(false)),
+            profiling: false,
+            profile: Profile::default(),
+            log_level: None,
+            events: RefCell::new(Vec::new()),
             sol1: None,
             sol2: None,
-            base_time: Instant::now(),
+            last_mark: Instant::now(),
+            last_alloc_mark: AllocMark::now(),
+            parse_time_ms: None,
             sol1_time_ms: 0.0,
             sol2_time_ms: 0.0,
+            parse_allocs: None,
+            sol1_allocs: None,
+            sol2_allocs: None,
+            on_progress: None,
         }
     }
 
@@ -28,22 +77,158 @@ impl Context {
         self.is_example
     }
 
+    pub fn set_part(&mut self, part: Option<u8>) {
+        self.part = part;
+    }
+
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|selected| selected == part)
+    }
+
+    pub fn set_params(&mut self, params: HashMap<String, String>) {
+        self.params = params;
+    }
+
+    // Value of a puzzle parameter that differs between the real input and the
+    // example, unless it was overridden with `--param name=value`
+    pub fn param<T>(&self, name: &'static str, real: T, example: T) -> crate::Result<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let mut used = self.used_params.borrow_mut();
+        if !used.contains(&name) {
+            used.push(name);
+        }
+        match self.params.get(name) {
+            Some(value) => value
+                .parse()
+                .map_err(|err: T::Err| Error::param(name, value, err.to_string())),
+            None if self.is_example => Ok(example),
+            None => Ok(real),
+        }
+    }
+
+    // Names of the parameters the solver asked for
+    pub fn used_params(&self) -> Vec<&'static str> {
+        self.used_params.borrow().clone()
+    }
+
+    // Long loops should poll this and give up with `Error::Timeout` once it's true
+    pub fn should_stop(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+
+    pub fn stop_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.stop)
+    }
+
+    // Lets the runner keep what was solved so far, e.g. part 1 when part 2
+    // runs out of time
+    pub fn on_progress<F: Fn(&Context) + Send + 'static>(&mut self, hook: F) {
+        self.on_progress = Some(Box::new(hook));
+    }
+
+    // Ends a phase: runs the hook, then starts timing the next phase, so that
+    // the hook itself isn't counted in it
+    fn report_progress(&mut self) {
+        if let Some(hook) = &self.on_progress {
+            hook(self);
+        }
+        self.start_timer();
+    }
+
+    pub fn set_profiling(&mut self, profiling: bool) {
+        self.profiling = profiling;
+    }
+
+    // Times the code until the returned guard is dropped, e.g.
+    // `let _span = ctx.span("build edges");`. Spans can be nested. They cost
+    // no more than a comparison when not profiling.
+    pub fn span(&self, name: &'static str) -> Span<'_> {
+        if self.profiling {
+            self.profile.enter(name)
+        } else {
+            Span::disabled()
+        }
+    }
+
+    pub fn profile(&self) -> Vec<SpanRecord> {
+        self.profile.records()
+    }
+
+    pub fn set_log_level(&mut self, level: Option<Level>) {
+        self.log_level = level;
+    }
+
+    // Diagnostics, kept aside from the answers and only shown with -v or -vv,
+    // e.g. `ctx.debug("merged circuits", &[("size", &size)])`. They cost no
+    // more than a comparison when disabled.
+    pub fn debug(&self, message: &str, fields: Fields) {
+        self.log(Level::Debug, message, fields);
+    }
+
+    pub fn trace(&self, message: &str, fields: Fields) {
+        self.log(Level::Trace, message, fields);
+    }
+
+    pub fn log_enabled(&self, level: Level) -> bool {
+        self.log_level.is_some_and(|max| level <= max)
+    }
+
+    pub fn events(&self) -> Vec<Event> {
+        self.events.borrow().clone()
+    }
+
+    fn log(&self, level: Level, message: &str, fields: Fields) {
+        if self.log_enabled(level) {
+            self.events
+                .borrow_mut()
+                .push(Event::new(level, message, fields));
+        }
+    }
+
     pub fn start_timer(&mut self) {
-        self.base_time = Instant::now();
+        self.last_alloc_mark = AllocMark::now();
+        self.last_mark = Instant::now();
     }
 
     pub fn input(&self) -> &[String] {
         &self.input
     }
 
+    // Ends the parse phase, so that parsing isn't counted as part of part 1
+    pub fn mark_parsed(&mut self) {
+        let (time_ms, allocs) = self.lap();
+        self.parse_time_ms = Some(time_ms);
+        self.parse_allocs = allocs;
+        self.report_progress();
+    }
+
+    // Runs `parser` on the input as the parse phase
+    pub fn parse<T, F>(&mut self, parser: F) -> T
+    where
+        F: FnOnce(&[String]) -> T,
+    {
+        let parsed = parser(&self.input);
+        self.mark_parsed();
+        parsed
+    }
+
     pub fn set_sol1<T: Into<Solution>>(&mut self, value: T) {
         self.sol1 = Some(value.into());
-        self.sol1_time_ms = self.base_time.elapsed().as_nanos() as f64 / 1_000_000.0;
+        (self.sol1_time_ms, self.sol1_allocs) = self.lap();
+        self.report_progress();
     }
 
     pub fn set_sol2<T: Into<Solution>>(&mut self, value: T) {
         self.sol2 = Some(value.into());
-        self.sol2_time_ms = self.base_time.elapsed().as_nanos() as f64 / 1_000_000.0;
+        (self.sol2_time_ms, self.sol2_allocs) = self.lap();
+        self.report_progress();
+    }
+
+    pub fn parse_time(&self) -> Option<f64> {
+        self.parse_time_ms
     }
 
     pub fn sol1(&self) -> Option<(&Solution, f64)> {
@@ -51,13 +236,31 @@ impl Context {
     }
 
     pub fn sol2(&self) -> Option<(&Solution, f64)> {
-        self.sol2
-            .as_ref()
-            .map(|s| (s, self.sol2_time_ms - self.sol1_time_ms))
+        self.sol2.as_ref().map(|s| (s, self.sol2_time_ms))
+    }
+
+    // Allocations of each phase, when they are counted
+    pub fn parse_allocs(&self) -> Option<AllocStats> {
+        self.parse_allocs
+    }
+
+    pub fn sol1_allocs(&self) -> Option<AllocStats> {
+        self.sol1_allocs
+    }
+
+    pub fn sol2_allocs(&self) -> Option<AllocStats> {
+        self.sol2_allocs
+    }
+
+    // Milliseconds and allocations since the last mark
+    fn lap(&self) -> (f64, Option<AllocStats>) {
+        let elapsed = self.last_mark.elapsed().as_nanos() as f64 / 1_000_000.0;
+        let allocs = self.last_alloc_mark.map(|mark| mark.elapsed());
+        (elapsed, allocs)
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Solution {
     I8(i8),
     I16(i16),
@@ -74,6 +277,49 @@ pub enum Solution {
     Str(String),
 }
 
+impl Solution {
+    // Name of the underlying type, e.g. "u64" or "str"
+    pub fn kind(&self) -> &'static str {
+        use Solution::*;
+        match self {
+            I8(_) => "i8",
+            I16(_) => "i16",
+            I32(_) => "i32",
+            I64(_) => "i64",
+            I128(_) => "i128",
+            Isize(_) => "isize",
+            U8(_) => "u8",
+            U16(_) => "u16",
+            U32(_) => "u32",
+            U64(_) => "u64",
+            U128(_) => "u128",
+            Usize(_) => "usize",
+            Str(_) => "str",
+        }
+    }
+
+    // Reverse of `kind` and `to_string`, e.g. to read back a stored answer
+    pub fn from_kind(kind: &str, value: &str) -> Option<Self> {
+        use Solution::*;
+        Some(match kind {
+            "i8" => I8(value.parse().ok()?),
+            "i16" => I16(value.parse().ok()?),
+            "i32" => I32(value.parse().ok()?),
+            "i64" => I64(value.parse().ok()?),
+            "i128" => I128(value.parse().ok()?),
+            "isize" => Isize(value.parse().ok()?),
+            "u8" => U8(value.parse().ok()?),
+            "u16" => U16(value.parse().ok()?),
+            "u32" => U32(value.parse().ok()?),
+            "u64" => U64(value.parse().ok()?),
+            "u128" => U128(value.parse().ok()?),
+            "usize" => Usize(value.parse().ok()?),
+            "str" => Str(value.to_string()),
+            _ => return None,
+        })
+    }
+}
+
 impl Display for Solution {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         use Solution::*;
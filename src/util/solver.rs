@@ -0,0 +1,75 @@
+use crate::util::error::*;
+use crate::util::solution::*;
+
+// A day's puzzle, split in phases so that the input is parsed only once and
+// shared by both parts. Each phase is timed on its own by the context.
+pub trait Solver: Sync {
+    type Parsed;
+
+    // Puzzle title, as shown on the Advent of Code website
+    const TITLE: &'static str;
+
+    // Number of implemented parts
+    const PARTS: u8 = 2;
+
+    // Name of this implementation, and the other implementations of the same
+    // day. Alternatives are only listed by the day's main solver.
+    const VARIANT: &'static str = "default";
+    const ALTERNATIVES: &'static [&'static dyn DaySolver] = &[];
+
+    fn parse(&self, input: &[String]) -> Result<Self::Parsed>;
+
+    fn part1(&self, parsed: &Self::Parsed, ctx: &Context) -> Result<Solution>;
+
+    // Only called when `PARTS` is 2
+    fn part2(&self, _parsed: &Self::Parsed, _ctx: &Context) -> Result<Solution> {
+        Err(Error::no_solution("part 2 is not implemented"))
+    }
+}
+
+// Object-safe view of a `Solver`, so that days with different `Parsed` types
+// can be stored side by side
+pub trait DaySolver: Sync {
+    fn title(&self) -> &'static str;
+
+    fn parts(&self) -> u8;
+
+    fn variant(&self) -> &'static str;
+
+    fn alternatives(&self) -> &'static [&'static dyn DaySolver];
+
+    fn run(&self, ctx: &mut Context) -> Result<()>;
+}
+
+impl<S: Solver> DaySolver for S {
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn parts(&self) -> u8 {
+        S::PARTS
+    }
+
+    fn variant(&self) -> &'static str {
+        S::VARIANT
+    }
+
+    fn alternatives(&self) -> &'static [&'static dyn DaySolver] {
+        S::ALTERNATIVES
+    }
+
+    fn run(&self, ctx: &mut Context) -> Result<()> {
+        // The context may have been created on another thread
+        ctx.start_timer();
+        let parsed = ctx.parse(|input| self.parse(input))?;
+        if ctx.runs_part(1) {
+            let sol1 = self.part1(&parsed, ctx)?;
+            ctx.set_sol1(sol1);
+        }
+        if S::PARTS > 1 && ctx.runs_part(2) {
+            let sol2 = self.part2(&parsed, ctx)?;
+            ctx.set_sol2(sol2);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use std::fmt::{self, Display};
+
+// Verbosity of the diagnostics, `Trace` being the most verbose
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Debug,
+    Trace,
+}
+
+// Diagnostic emitted by a solver through `Context::debug` or `Context::trace`
+#[derive(Clone, Debug)]
+pub struct Event {
+    pub level: Level,
+    pub message: String,
+    pub fields: Vec<(&'static str, String)>,
+}
+
+// Key-value pairs attached to an event, e.g. `&[("size", &circuit.len())]`
+pub type Fields<'a> = &'a [(&'static str, &'a dyn Display)];
+
+impl Event {
+    pub fn new(level: Level, message: &str, fields: Fields) -> Self {
+        Event {
+            level,
+            message: message.to_string(),
+            fields: fields
+                .iter()
+                .map(|(key, value)| (*key, value.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Level::Debug => write!(f, "DEBUG"),
+            Level::Trace => write!(f, "TRACE"),
+        }
+    }
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.level, self.message)?;
+        for (key, value) in &self.fields {
+            write!(f, " {}={}", key, value)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use crate::cli::Options;
+use crate::report::{self, DayReport};
+use crate::{pool, selected_solver, solve_day};
+
+use aoc::{InputSource, example};
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// Answer and time of each part of the previous run, by day and part
+type Previous = HashMap<(u8, u8), (String, f64)>;
+
+// Runs the selected days, then again every time one of their input, example or
+// answers files changes. Only stops with Ctrl-C.
+pub fn watch(options: &Options) -> ! {
+    let paths = watched_paths(options);
+    let mut previous = Previous::new();
+    let mut last_seen = None;
+    loop {
+        let seen = modification_times(&paths);
+        if last_seen.as_ref() != Some(&seen) {
+            last_seen = Some(seen);
+            // Clears the screen and moves the cursor to the top left corner
+            print!("\x1b[2J\x1b[H");
+            run(options, &mut previous);
+            println!(
+                "Watching {} file(s) for changes, press Ctrl-C to stop",
+                paths.len()
+            );
+            let _ = io::stdout().flush();
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn run(options: &Options, previous: &mut Previous) {
+    let mut changes = Vec::new();
+    pool::for_each_ordered(
+        &options.days,
+        options.jobs,
+        |&day| solve_day(day, selected_solver(day, options), options, None),
+        |report| {
+            report::print_text(&report);
+            changes.extend(diff(previous, &report));
+        },
+    );
+    if !changes.is_empty() {
+        println!("Since the previous run:");
+        for change in &changes {
+            println!("  · {}", change);
+        }
+        println!();
+    }
+}
+
+// Describes how each part of `report` differs from the previous run, and
+// remembers it for the next one
+fn diff(previous: &mut Previous, report: &DayReport) -> Vec<String> {
+    let mut changes = Vec::new();
+    for sol in report.parts.iter().flatten() {
+        let answer = sol.solution.to_string();
+        let elapsed_ms = sol.timing.elapsed_ms();
+        let part = format!("Day {:02} part {}", report.day, sol.part);
+        if let Some((previous_answer, previous_ms)) = previous.get(&(report.day, sol.part)) {
+            let answer_change = if *previous_answer == answer {
+                String::new()
+            } else {
+                format!("answer changed to {}, was {}; ", answer, previous_answer)
+            };
+            changes.push(format!(
+                "{}: {}{:.4} ms, was {:.4} ms ({:+.1}%)",
+                part,
+                answer_change,
+                elapsed_ms,
+                previous_ms,
+                (elapsed_ms / previous_ms - 1.0) * 100.0
+            ));
+        }
+        previous.insert((report.day, sol.part), (answer, elapsed_ms));
+    }
+    changes
+}
+
+// Every file a run of the selected days reads, whether it exists or not, so
+// that creating one also counts as a change
+fn watched_paths(options: &Options) -> Vec<PathBuf> {
+    let default_source = InputSource::default_dir();
+    let source = options.input.as_ref().unwrap_or(&default_source);
+    let mut paths = Vec::new();
+    for &day in &options.days {
+        paths.extend(source.candidate_paths(day));
+        paths.extend(source.answers_path(day));
+        for part in [1, 2] {
+            paths.extend(example::candidate_paths(day, part));
+        }
+        paths.push(example::answers_path(day));
+    }
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+fn modification_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| fs::metadata(path).and_then(|meta| meta.modified()).ok())
+        .collect()
+}
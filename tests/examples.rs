@@ -0,0 +1,110 @@
+use aoc::days::*;
+use aoc::{Context, DaySolver, example};
+
+use std::fs;
+
+// Runs `solver` on the examples of `day` and checks the answers of each part
+// against `inputs/examples/dayNN.answers`
+fn run(solver: &dyn DaySolver, day: u8) {
+    let expected = expected_answers(day);
+    let mut last_run = None;
+    for part in [1, 2] {
+        let path = example::example_path(day, part).expect("missing example input");
+        // Both parts usually share the same example, which is only solved once
+        if last_run
+            .as_ref()
+            .is_none_or(|(last_path, _)| *last_path != path)
+        {
+            let input = aoc::read_lines_as_vec(&path).expect("unreadable example input");
+            let mut ctx = Context::new(input, true);
+            solver.run(&mut ctx).expect("solver failed on the example");
+            last_run = Some((path, ctx));
+        }
+        let ctx = &last_run.as_ref().unwrap().1;
+        let sol = match part {
+            1 => ctx.sol1(),
+            _ => ctx.sol2(),
+        };
+        assert_eq!(
+            sol.map(|(sol, _)| sol.to_string()),
+            expected[part as usize - 1],
+            "day {} part {}",
+            day,
+            part
+        );
+    }
+}
+
+// Answers of each part, in the `part1: <answer>` format the runner reads
+fn expected_answers(day: u8) -> [Option<String>; 2] {
+    let path = example::answers_path(day);
+    let content = fs::read_to_string(&path).expect("missing example answers");
+    let answer = |part: &str| {
+        content.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            (name == part).then(|| value.trim().to_string())
+        })
+    };
+    [answer("part1"), answer("part2")]
+}
+
+#[test]
+fn day01_example() {
+    run(&day01::Day01, 1);
+}
+
+#[test]
+fn day02_example() {
+    run(&day02::Day02, 2);
+}
+
+#[test]
+fn day03_example() {
+    run(&day03::Day03, 3);
+}
+
+#[test]
+fn day04_example() {
+    run(&day04::Day04, 4);
+}
+
+#[test]
+fn day05_example() {
+    run(&day05::Day05, 5);
+}
+
+#[test]
+fn day06_example() {
+    run(&day06::Day06, 6);
+}
+
+#[test]
+fn day07_example() {
+    run(&day07::Day07, 7);
+}
+
+#[test]
+fn day08_example() {
+    run(&day08::Day08, 8);
+}
+
+#[test]
+fn day09_example() {
+    run(&day09::Day09, 9);
+}
+
+#[test]
+fn day09_compressed_example() {
+    run(&day09::Day09Compressed, 9);
+}
+
+#[test]
+fn day10_example() {
+    run(&day10::Day10, 10);
+}
+
+#[test]
+fn day11_example() {
+    // Each part has its own example
+    run(&day11::Day11, 11);
+}
@@ -0,0 +1,126 @@
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+// Minimal stand-in for the Advent of Code server: day 3 has an input, day 5 is
+// rate limited and every other day is missing. Returns its URL and the request
+// line and headers of every request it received.
+fn stub_server() -> (String, Arc<Mutex<Vec<Vec<String>>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let received = Arc::clone(&requests);
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let lines: Vec<String> = BufReader::new(&stream)
+                .lines()
+                .map(Result::unwrap)
+                .take_while(|line| !line.is_empty())
+                .collect();
+            let response = match lines[0].split(' ').nth(1) {
+                Some("/2025/day/3/input") => {
+                    "HTTP/1.1 200 OK\r\nContent-Length: 8\r\n\r\n1,2\n3,4\n"
+                }
+                Some("/2025/day/5/input") => {
+                    "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 60\r\nContent-Length: 0\r\n\r\n"
+                }
+                _ => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n",
+            };
+            received.lock().unwrap().push(lines);
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    (url, requests)
+}
+
+// Empty working directory of its own for each test
+fn work_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-fetch-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn fetch(dir: &PathBuf, url: &str, days: &[&str]) -> Output {
+    // Requests are otherwise spaced by a few seconds, even across runs
+    let _ = fs::remove_file(dir.join(".aoc/last_fetch"));
+    Command::new(env!("CARGO_BIN_EXE_advent_of_code_2025"))
+        .arg("fetch")
+        .args(days)
+        .current_dir(dir)
+        .env_remove("AOC_INPUT_DIR")
+        .env("AOC_BASE_URL", url)
+        .env("AOC_SESSION", "secret")
+        .env("AOC_CONTACT", "someone@example.com")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn fetch_downloads_once() {
+    let (url, requests) = stub_server();
+    let dir = work_dir("once");
+
+    let output = fetch(&dir, &url, &["3"]);
+    assert!(output.status.success());
+    assert_eq!(
+        fs::read_to_string(dir.join("inputs/day03.txt")).unwrap(),
+        "1,2\n3,4\n"
+    );
+    let headers = requests.lock().unwrap()[0].clone();
+    assert_eq!(headers[0], "GET /2025/day/3/input HTTP/1.1");
+    assert!(
+        headers
+            .iter()
+            .any(|h| h.eq_ignore_ascii_case("cookie: session=secret"))
+    );
+    assert!(headers.iter().any(|h| {
+        h.to_ascii_lowercase().starts_with("user-agent:") && h.contains("someone@example.com")
+    }));
+
+    // Already there, the server isn't asked again
+    let output = fetch(&dir, &url, &["3"]);
+    assert!(output.status.success());
+    assert_eq!(requests.lock().unwrap().len(), 1);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn fetch_stops_when_rate_limited() {
+    let (url, requests) = stub_server();
+    let dir = work_dir("rate-limited");
+
+    let output = fetch(&dir, &url, &["5", "6"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("rate limited"));
+    // Day 6 is not requested after the server asked to slow down
+    assert_eq!(requests.lock().unwrap().len(), 1);
+    assert!(!dir.join("inputs/day05.txt").exists());
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn fetch_without_token_fails_once() {
+    let (url, requests) = stub_server();
+    let dir = work_dir("no-token");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_advent_of_code_2025"))
+        .args(["fetch", "3", "4"])
+        .current_dir(&dir)
+        .env_remove("AOC_INPUT_DIR")
+        .env_remove("AOC_SESSION")
+        .env("AOC_BASE_URL", &url)
+        .env("AOC_CONTACT", "someone@example.com")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(stderr.matches("No session token").count(), 1);
+    assert!(requests.lock().unwrap().is_empty());
+    let _ = fs::remove_dir_all(&dir);
+}